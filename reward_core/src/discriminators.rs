@@ -6,4 +6,6 @@ pub enum Discriminators {
     EpochRewardDistributionMarker = 3,
     RewardDropbox = 4,
     RewardConfig = 5,
+    EpochRewardTicketVaultMarker = 6,
+    EpochRewardSlashMarker = 7,
 }
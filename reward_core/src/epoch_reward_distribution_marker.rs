@@ -1,20 +1,39 @@
 use bytemuck::{Pod, Zeroable};
-use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
 use shank::ShankAccount;
-use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::discriminators::Discriminators;
 
-// Empty struct to mark the epoch reward distribution
+/// Marks that a payout of the epoch reward distribution was made
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable, AccountDeserialize, ShankAccount)]
 #[repr(C)]
-pub struct EpochRewardDistributionMarker {}
+pub struct EpochRewardDistributionMarker {
+    pub epoch_reward_merkle_root: Pubkey,
+    /// Paid the rent for the marker, and gets it back when the marker is closed
+    pub payer: Pubkey,
+    pub slot_created: PodU64,
+}
 
 impl Discriminator for EpochRewardDistributionMarker {
     const DISCRIMINATOR: u8 = Discriminators::EpochRewardDistributionMarker as u8;
 }
 
 impl EpochRewardDistributionMarker {
+    pub fn size() -> u64 {
+        8_u64
+            .checked_add(std::mem::size_of::<EpochRewardDistributionMarker>() as u64)
+            .unwrap()
+    }
+
+    pub fn new(epoch_reward_merkle_root: Pubkey, payer: Pubkey, slot_created: u64) -> Self {
+        Self {
+            epoch_reward_merkle_root,
+            payer,
+            slot_created: PodU64::from(slot_created),
+        }
+    }
+
     /// Returns the seeds for the PDA
     pub fn seeds(
         epoch_reward_merkle_root: &Pubkey,
@@ -44,4 +63,30 @@ impl EpochRewardDistributionMarker {
         let (pda, bump) = Pubkey::find_program_address(&seeds_iter, program_id);
         (pda, bump, seeds)
     }
+
+    /// The PDA can't be checked without the mint and recipient, the owner and
+    /// discriminator guarantee the marker was created by the program
+    pub fn load(
+        program_id: &Pubkey,
+        account: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<(), ProgramError> {
+        if account.owner.ne(program_id) {
+            msg!("Epoch reward distribution marker account has an invalid owner");
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if account.data_is_empty() {
+            msg!("Epoch reward distribution marker account data is empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if expect_writable && !account.is_writable {
+            msg!("Epoch reward distribution marker account is not writable");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account.data.borrow()[0].ne(&Self::DISCRIMINATOR) {
+            msg!("Epoch reward distribution marker account discriminator is invalid");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
 }
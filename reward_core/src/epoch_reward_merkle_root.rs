@@ -1,9 +1,19 @@
 use bytemuck::{Pod, Zeroable};
-use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use jito_bytemuck::{
+    types::{PodU128, PodU64},
+    AccountDeserialize, Discriminator,
+};
+use jito_weight_table_core::{weight::Weight, weight_table::WeightTable};
 use shank::{ShankAccount, ShankType};
-use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
 
-use crate::{discriminators::Discriminators, merkle_root::MerkleRoot, token_table::TokenTable};
+use crate::{
+    discriminators::Discriminators,
+    error::RewardCoreError,
+    merkle_root::MerkleRoot,
+    reward_config::RewardConfig,
+    token_table::{StakeTable, TokenTable},
+};
 
 /// The vault is responsible for holding tokens and minting VRT tokens
 /// based on the amount of tokens deposited.
@@ -13,8 +23,27 @@ use crate::{discriminators::Discriminators, merkle_root::MerkleRoot, token_table
 pub struct EpochRewardMerkleRoot {
     pub ncn: Pubkey,
     pub ncn_epoch: PodU64,
+    pub epoch_start_slot: PodU64,    // First slot of the NCN epoch
+    pub epoch_length: PodU64,        // Restaking config epoch length when the account was created
     pub reward_payout_count: PodU64, // How many times the `distribute_crank` was called
     pub roots: [MerkleRootEntry; 32],
+    pub deposited_rewards: TokenTable, // Total rewards deposited per mint, native SOL uses the native mint
+    pub distributed_rewards: TokenTable, // Total rewards paid out per mint by the `distribute_crank`
+    state: u8,                           // EpochRewardState, only changed through `transition`
+    pub slot_distribution_finished: PodU64, // Slot every deposited reward was paid out, marker accounts can be closed after it
+    pub consensus_root: MerkleRoot, // Root that reached consensus, empty if voting finalized without consensus
+    pub voted_stake: PodU128, // Normalized stake that voted for any root, recorded at finalization
+    pub consensus_stake: PodU128, // Normalized stake behind `consensus_root`
+    pub registered_stake: StakeTable, // Total stake registered on operator tickets per mint, voted or not
+    pub registered_normalized_stake: PodU128, // Normalized `registered_stake`, recorded at finalization
+    valid_voting_slots: PodU64, // `RewardConfig` values when the account was created, later config updates do not apply
+    slots_before_closing_marker_accounts: PodU64,
+    consensus_threshold: Weight,
+    min_participation: Weight,
+    stake_snapshot_slots: PodU64,
+    claim_window_slots: PodU64,
+    fee_wallet: Pubkey,
+    fee_bps: PodU64,
 }
 
 #[derive(Debug, Clone, Copy, Zeroable, ShankType, Pod, Default)]
@@ -24,12 +53,111 @@ pub struct MerkleRootEntry {
     pub stake: TokenTable,
 }
 
+impl MerkleRootEntry {
+    pub fn is_empty(&self) -> bool {
+        self.root.is_empty()
+    }
+}
+
+/// Lifecycle of an epoch's rewards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EpochRewardState {
+    /// The epoch is running, rewards can be deposited and operators register their stake
+    AcceptingDeposits = 0,
+    /// The epoch ended, operators vote for a merkle root
+    Voting = 1,
+    /// Voting is over and the outcome is recorded, with or without a consensus root
+    Finalized = 2,
+    /// Rewards are being paid out
+    Distributing = 3,
+    /// Every deposited reward was paid out, or what was left moved to a later epoch once the
+    /// claim window closed
+    Distributed = 4,
+    /// Voting finalized without consensus and the rewards moved to a later epoch
+    RolledOver = 5,
+}
+
+impl EpochRewardState {
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::AcceptingDeposits, Self::Voting)
+                | (Self::Voting, Self::Finalized)
+                | (Self::Finalized, Self::Distributing)
+                | (Self::Finalized, Self::RolledOver)
+                | (Self::Finalized, Self::Distributed)
+                | (Self::Distributing, Self::Distributed)
+        )
+    }
+}
+
+impl TryFrom<u8> for EpochRewardState {
+    type Error = RewardCoreError;
+
+    fn try_from(state: u8) -> Result<Self, Self::Error> {
+        match state {
+            0 => Ok(Self::AcceptingDeposits),
+            1 => Ok(Self::Voting),
+            2 => Ok(Self::Finalized),
+            3 => Ok(Self::Distributing),
+            4 => Ok(Self::Distributed),
+            5 => Ok(Self::RolledOver),
+            _ => Err(RewardCoreError::InvalidState),
+        }
+    }
+}
+
+/// The outcome of tallying the stake-weighted votes of an epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootConsensus {
+    /// The root with the most normalized stake behind it
+    pub root: MerkleRoot,
+    /// Normalized stake that voted for `root`
+    pub root_stake: u128,
+    /// Normalized stake that voted for any root
+    pub total_stake: u128,
+    /// Normalized stake registered on operator tickets, whether it voted or not
+    pub registered_stake: u128,
+}
+
+impl RootConsensus {
+    /// Whether `root` has at least `consensus_threshold` of the registered stake behind it and
+    /// the voted stake is at least `min_participation` of the registered stake. Stake that did
+    /// not vote counts against the root, so a root that is only leading returns false.
+    /// Overflow is treated as not reached.
+    pub fn consensus_reached(
+        &self,
+        consensus_threshold: &Weight,
+        min_participation: &Weight,
+    ) -> bool {
+        if self.total_stake == 0 || self.registered_stake == 0 {
+            return false;
+        }
+
+        Self::meets_fraction(self.root_stake, self.registered_stake, consensus_threshold)
+            && Self::meets_fraction(self.total_stake, self.registered_stake, min_participation)
+    }
+
+    /// Whether `part` is at least `fraction` of `whole`
+    fn meets_fraction(part: u128, whole: u128, fraction: &Weight) -> bool {
+        part.checked_mul(fraction.denominator() as u128)
+            .and_then(|part| {
+                whole
+                    .checked_mul(fraction.numerator() as u128)
+                    .map(|threshold| part >= threshold)
+            })
+            .unwrap_or(false)
+    }
+}
+
 impl Discriminator for EpochRewardMerkleRoot {
     const DISCRIMINATOR: u8 = Discriminators::EpochRewardMerkleRoot as u8;
 }
 
 impl EpochRewardMerkleRoot {
     pub const MAX_ROOTS: usize = 32;
+    pub const DISTRIBUTION_NOT_FINISHED: u64 = u64::MAX;
 
     pub fn size() -> u64 {
         8_u64
@@ -38,15 +166,81 @@ impl EpochRewardMerkleRoot {
     }
 
     /// Create a new vault account
-    pub fn new(ncn: Pubkey, ncn_epoch: u64) -> Self {
+    pub fn new(ncn: Pubkey, ncn_epoch: u64, epoch_start_slot: u64, epoch_length: u64) -> Self {
         Self {
             ncn,
             ncn_epoch: PodU64::from(ncn_epoch),
+            epoch_start_slot: PodU64::from(epoch_start_slot),
+            epoch_length: PodU64::from(epoch_length),
             reward_payout_count: PodU64::default(),
             roots: [MerkleRootEntry::default(); Self::MAX_ROOTS],
+            deposited_rewards: TokenTable::new(),
+            distributed_rewards: TokenTable::new(),
+            state: EpochRewardState::AcceptingDeposits as u8,
+            slot_distribution_finished: PodU64::from(Self::DISTRIBUTION_NOT_FINISHED),
+            consensus_root: MerkleRoot::default(),
+            voted_stake: PodU128::from(0),
+            consensus_stake: PodU128::from(0),
+            registered_stake: StakeTable::new(),
+            registered_normalized_stake: PodU128::from(0),
+            valid_voting_slots: PodU64::default(),
+            slots_before_closing_marker_accounts: PodU64::default(),
+            consensus_threshold: Weight::default(),
+            min_participation: Weight::default(),
+            stake_snapshot_slots: PodU64::default(),
+            claim_window_slots: PodU64::default(),
+            fee_wallet: Pubkey::default(),
+            fee_bps: PodU64::default(),
         }
     }
 
+    /// Copies the voting parameters of `reward_config`, the epoch keeps them for its whole
+    /// lifetime so an `UpdateConfig` cannot move the deadlines or threshold of a live epoch
+    pub fn snapshot_config(&mut self, reward_config: &RewardConfig) {
+        self.valid_voting_slots = reward_config.valid_voting_slots;
+        self.slots_before_closing_marker_accounts =
+            reward_config.slots_before_closing_marker_accounts;
+        self.consensus_threshold = reward_config.consensus_threshold();
+        self.min_participation = reward_config.min_participation();
+        self.stake_snapshot_slots = reward_config.stake_snapshot_slots;
+        self.claim_window_slots = reward_config.claim_window_slots;
+        self.fee_wallet = reward_config.fee_wallet;
+        self.fee_bps = PodU64::from(reward_config.fee_bps());
+    }
+
+    pub fn valid_voting_slots(&self) -> u64 {
+        self.valid_voting_slots.into()
+    }
+
+    pub fn slots_before_closing_marker_accounts(&self) -> u64 {
+        self.slots_before_closing_marker_accounts.into()
+    }
+
+    pub fn claim_window_slots(&self) -> u64 {
+        self.claim_window_slots.into()
+    }
+
+    /// Operators building the epoch's root pay `fee_bps` of its rewards to this wallet
+    pub fn fee_wallet(&self) -> Pubkey {
+        self.fee_wallet
+    }
+
+    pub fn fee_bps(&self) -> u64 {
+        self.fee_bps.into()
+    }
+
+    pub fn consensus_threshold(&self) -> Weight {
+        self.consensus_threshold
+    }
+
+    pub fn min_participation(&self) -> Weight {
+        self.min_participation
+    }
+
+    pub fn stake_snapshot_slots(&self) -> u64 {
+        self.stake_snapshot_slots.into()
+    }
+
     pub fn epoch(current_slot: u64, epoch_length: u64) -> Option<u64> {
         current_slot.checked_div(epoch_length)
     }
@@ -72,15 +266,1163 @@ impl EpochRewardMerkleRoot {
         (pda, bump, seeds)
     }
 
+    pub fn load(
+        program_id: &Pubkey,
+        ncn: &Pubkey,
+        account: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<(), ProgramError> {
+        if account.owner.ne(program_id) {
+            msg!("Epoch reward merkle root account has an invalid owner");
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if account.data_is_empty() {
+            msg!("Epoch reward merkle root account data is empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if expect_writable && !account.is_writable {
+            msg!("Epoch reward merkle root account is not writable");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account.data.borrow()[0].ne(&Self::DISCRIMINATOR) {
+            msg!("Epoch reward merkle root account discriminator is invalid");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let ncn_epoch = {
+            let account_data = account.data.borrow();
+            let epoch_reward_merkle_root = Self::try_from_slice_unchecked(&account_data)?;
+            u64::from(epoch_reward_merkle_root.ncn_epoch)
+        };
+        if account
+            .key
+            .ne(&Self::find_program_address(program_id, ncn, ncn_epoch).0)
+        {
+            msg!("Epoch reward merkle root account is not at the correct PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    pub fn epoch_start_slot(&self) -> u64 {
+        self.epoch_start_slot.into()
+    }
+
+    pub fn epoch_length(&self) -> u64 {
+        self.epoch_length.into()
+    }
+
+    /// The last slot of the NCN epoch, voting opens after it
+    pub fn epoch_last_slot(&self) -> u64 {
+        self.epoch_start_slot()
+            .saturating_add(self.epoch_length().saturating_sub(1))
+    }
+
+    /// Voting is open for `valid_voting_slots` slots after the last slot of the epoch.
+    /// An overflowing window never closes.
+    /// Whether tickets can still be created, only during the first `stake_snapshot_slots` of the
+    /// epoch so the stake read from the delegations is a snapshot of the epoch start
+    pub fn is_stake_snapshot_open(&self, current_slot: u64) -> bool {
+        current_slot >= self.epoch_start_slot()
+            && current_slot <= self.epoch_last_slot()
+            && current_slot - self.epoch_start_slot() < self.stake_snapshot_slots()
+    }
+
     pub fn is_voting_done(&self, current_slot: u64, valid_voting_slots: u64) -> bool {
-        let voting_cutoff = u64::from(self.ncn_epoch)
+        self.epoch_last_slot()
             .checked_add(valid_voting_slots)
+            .is_some_and(|voting_last_slot| voting_last_slot < current_slot)
+    }
+
+    pub fn state(&self) -> Result<EpochRewardState, RewardCoreError> {
+        EpochRewardState::try_from(self.state)
+    }
+
+    /// Moves to `next`, failing if the lifecycle does not allow it. A finalized epoch is
+    /// distributed if it has a consensus root and rolled over otherwise.
+    pub fn transition(&mut self, next: EpochRewardState) -> Result<(), RewardCoreError> {
+        let state = self.state()?;
+        if !state.can_transition_to(next) {
+            return Err(RewardCoreError::InvalidStateTransition);
+        }
+
+        let has_consensus_root = !self.consensus_root.is_empty();
+        match (state, next) {
+            (
+                EpochRewardState::Finalized,
+                EpochRewardState::Distributing | EpochRewardState::Distributed,
+            ) if !has_consensus_root => {
+                return Err(RewardCoreError::InvalidStateTransition);
+            }
+            (EpochRewardState::Finalized, EpochRewardState::RolledOver) if has_consensus_root => {
+                return Err(RewardCoreError::InvalidStateTransition);
+            }
+            _ => {}
+        }
+
+        self.state = next as u8;
+        Ok(())
+    }
+
+    /// Whether voting can be finalized at `current_slot`. Before the voting window is over,
+    /// `tally` has to reach consensus against the registered stake, so a root with only part
+    /// of the stake behind it waits for the window to end and the other operators can vote.
+    pub fn can_finalize(
+        &self,
+        tally: Option<&RootConsensus>,
+        current_slot: u64,
+        valid_voting_slots: u64,
+        consensus_threshold: &Weight,
+        min_participation: &Weight,
+    ) -> bool {
+        self.state() == Ok(EpochRewardState::Voting)
+            && (self.is_voting_done(current_slot, valid_voting_slots)
+                || tally.is_some_and(|tally| {
+                    tally.consensus_reached(consensus_threshold, min_participation)
+                }))
+    }
+
+    /// Records the outcome of voting and finalizes the epoch. `tally` is the highest voted
+    /// root, it only becomes the consensus root if it reached the configured thresholds.
+    pub fn finalize_consensus(
+        &mut self,
+        tally: Option<RootConsensus>,
+        consensus_threshold: &Weight,
+        min_participation: &Weight,
+    ) -> Result<(), RewardCoreError> {
+        if self.state()? != EpochRewardState::Voting {
+            return Err(RewardCoreError::InvalidStateTransition);
+        }
+
+        self.voted_stake = PodU128::from(tally.map_or(0, |tally| tally.total_stake));
+        self.registered_normalized_stake =
+            PodU128::from(tally.map_or(0, |tally| tally.registered_stake));
+        match tally.filter(|tally| tally.consensus_reached(consensus_threshold, min_participation))
+        {
+            Some(consensus) => {
+                self.consensus_root = consensus.root;
+                self.consensus_stake = PodU128::from(consensus.root_stake);
+            }
+            None => {
+                self.consensus_root = MerkleRoot::default();
+                self.consensus_stake = PodU128::from(0);
+            }
+        }
+
+        self.transition(EpochRewardState::Finalized)
+    }
+
+    /// The recorded consensus, `None` before finalization or if voting finalized without one
+    pub fn consensus(&self) -> Option<RootConsensus> {
+        let finalized = matches!(
+            self.state(),
+            Ok(EpochRewardState::Finalized
+                | EpochRewardState::Distributing
+                | EpochRewardState::Distributed)
+        );
+
+        if !finalized || self.consensus_root.is_empty() {
+            return None;
+        }
+
+        Some(RootConsensus {
+            root: self.consensus_root,
+            root_stake: self.consensus_stake.into(),
+            total_stake: self.voted_stake.into(),
+            registered_stake: self.registered_normalized_stake.into(),
+        })
+    }
+
+    pub fn voted_stake(&self) -> u128 {
+        self.voted_stake.into()
+    }
+
+    pub fn consensus_stake(&self) -> u128 {
+        self.consensus_stake.into()
+    }
+
+    pub fn registered_normalized_stake(&self) -> u128 {
+        self.registered_normalized_stake.into()
+    }
+
+    /// Registers stake counted on an operator's ticket, the base that participation is
+    /// measured against. Frozen once voting opens.
+    pub fn register_stake(&mut self, mint: &Pubkey, amount: u64) -> Result<(), RewardCoreError> {
+        if self.state()? != EpochRewardState::AcceptingDeposits {
+            return Err(RewardCoreError::InvalidState);
+        }
+
+        self.registered_stake.add_value(mint, amount)
+    }
+
+    /// Opens voting once the epoch's last slot has passed, deposits are no longer accepted
+    /// after it. Does nothing in any other state.
+    pub fn open_voting(&mut self, current_slot: u64) -> Result<(), RewardCoreError> {
+        if self.state()? == EpochRewardState::AcceptingDeposits
+            && current_slot > self.epoch_last_slot()
+        {
+            self.transition(EpochRewardState::Voting)?;
+        }
+        Ok(())
+    }
+
+    pub fn rolled_over(&self) -> bool {
+        self.state() == Ok(EpochRewardState::RolledOver)
+    }
+
+    /// Whether the epoch still needs its weight table. The table is only read to tally the
+    /// votes, so it is no longer needed once voting is finalized, however the rewards end.
+    pub fn needs_weight_table(&self) -> bool {
+        !matches!(
+            self.state(),
+            Ok(EpochRewardState::Finalized
+                | EpochRewardState::Distributing
+                | EpochRewardState::Distributed
+                | EpochRewardState::RolledOver)
+        )
+    }
+
+    /// Whether any operator has voted for a root this epoch
+    pub fn has_votes(&self) -> bool {
+        self.roots.iter().any(|entry| !entry.is_empty())
+    }
+
+    /// Adds `stake` to the votes for `root`, taking a new entry if the root is new
+    pub fn add_vote(
+        &mut self,
+        root: &MerkleRoot,
+        stake: &StakeTable,
+    ) -> Result<(), RewardCoreError> {
+        let entry = self
+            .roots
+            .iter_mut()
+            .find(|entry| entry.root == *root || entry.is_empty())
+            .ok_or(RewardCoreError::NoMoreRootSlots)?;
+
+        if entry.is_empty() {
+            entry.root = *root;
+        }
+
+        entry.stake.add_table(stake)
+    }
+
+    /// Removes `stake` from the votes for `root`, used when an operator changes their vote
+    pub fn remove_vote(
+        &mut self,
+        root: &MerkleRoot,
+        stake: &StakeTable,
+    ) -> Result<(), RewardCoreError> {
+        let entry = self
+            .roots
+            .iter_mut()
+            .find(|entry| !entry.is_empty() && entry.root == *root)
+            .ok_or(RewardCoreError::RootNotFound)?;
+
+        entry.stake.sub_table(stake)
+    }
+
+    /// Records a payout of `amount` of `mint`. Payouts can never exceed what was
+    /// deposited for the mint, whatever the merkle root contains. Once every deposited
+    /// mint is fully paid out, the distribution is finished at `current_slot`. Only allowed
+    /// once the epoch is finalized.
+    pub fn record_distribution(
+        &mut self,
+        mint: &Pubkey,
+        amount: u64,
+        current_slot: u64,
+    ) -> Result<(), RewardCoreError> {
+        let state = self.state()?;
+        if !matches!(
+            state,
+            EpochRewardState::Finalized | EpochRewardState::Distributing
+        ) {
+            return Err(RewardCoreError::InvalidStateTransition);
+        }
+
+        let deposited = self.deposited_rewards.find_value(mint).unwrap_or(0);
+        let distributed = self
+            .distributed_rewards
+            .find_value(mint)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(RewardCoreError::ArithmeticOverflow)?;
+
+        if distributed > deposited {
+            return Err(RewardCoreError::InsufficientRewards);
+        }
+
+        self.distributed_rewards.set_value(mint, distributed)?;
+        self.reward_payout_count = PodU64::from(
+            u64::from(self.reward_payout_count)
+                .checked_add(1)
+                .ok_or(RewardCoreError::ArithmeticOverflow)?,
+        );
+
+        if state == EpochRewardState::Finalized {
+            self.transition(EpochRewardState::Distributing)?;
+        }
+
+        if self.fully_distributed() {
+            self.slot_distribution_finished = PodU64::from(current_slot);
+            self.transition(EpochRewardState::Distributed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether every deposited mint is fully paid out, which holds for an epoch without deposits
+    fn fully_distributed(&self) -> bool {
+        self.deposited_rewards
+            .table
+            .iter()
+            .filter(|entry| !entry.is_empty())
+            .all(|entry| {
+                self.distributed_rewards.find_value(&entry.mint) == Some(entry.value.into())
+            })
+    }
+
+    /// Whether the distribution of an epoch with a consensus root can be ended with what is left
+    /// undistributed. That is once nothing is left to pay out, for example without deposits, or
+    /// once the claim window of `claim_slots` after the voting window is over.
+    pub fn can_finish_distribution(
+        &self,
+        current_slot: u64,
+        valid_voting_slots: u64,
+        claim_slots: u64,
+    ) -> bool {
+        let distributing = matches!(
+            self.state(),
+            Ok(EpochRewardState::Finalized | EpochRewardState::Distributing)
+        );
+
+        distributing
+            && !self.consensus_root.is_empty()
+            && (self.fully_distributed()
+                || self
+                    .is_voting_done(current_slot, valid_voting_slots.saturating_add(claim_slots)))
+    }
+
+    /// Ends the distribution at `current_slot`, the caller moves out whatever was not paid out
+    pub fn finish_distribution(&mut self, current_slot: u64) -> Result<(), RewardCoreError> {
+        if !matches!(
+            self.state()?,
+            EpochRewardState::Finalized | EpochRewardState::Distributing
+        ) {
+            return Err(RewardCoreError::InvalidStateTransition);
+        }
+
+        self.transition(EpochRewardState::Distributed)?;
+        self.slot_distribution_finished = PodU64::from(current_slot);
+        Ok(())
+    }
+
+    pub fn slot_distribution_finished(&self) -> u64 {
+        self.slot_distribution_finished.into()
+    }
+
+    pub fn distribution_finished(&self) -> bool {
+        self.state() == Ok(EpochRewardState::Distributed)
+    }
+
+    /// Sums the stake behind `stake` after normalizing each mint with the weight table.
+    /// Mints without a weight do not count towards the total. Each mint's contribution
+    /// is rounded down.
+    pub fn normalized_stake(
+        stake: &TokenTable,
+        weight_table: &WeightTable,
+    ) -> Result<u128, RewardCoreError> {
+        weight_table
+            .normalize_stake(stake.iter())
+            .map_err(|_| RewardCoreError::ArithmeticOverflow)
+    }
+
+    /// Tallies the votes of every root using the normalized stake.
+    /// Returns `None` if no stake has voted, ties go to the root voted on first.
+    pub fn get_highest_voted_root(
+        &self,
+        weight_table: &WeightTable,
+    ) -> Result<Option<RootConsensus>, RewardCoreError> {
+        let mut highest: Option<(MerkleRoot, u128)> = None;
+        let mut total_stake: u128 = 0;
+
+        for entry in self.roots.iter().filter(|entry| !entry.is_empty()) {
+            let stake = Self::normalized_stake(&entry.stake, weight_table)?;
+
+            total_stake = total_stake
+                .checked_add(stake)
+                .ok_or(RewardCoreError::ArithmeticOverflow)?;
+
+            match highest {
+                Some((_, highest_stake)) if highest_stake >= stake => {}
+                _ => highest = Some((entry.root, stake)),
+            }
+        }
+
+        if total_stake == 0 {
+            return Ok(None);
+        }
+
+        let registered_stake = Self::normalized_stake(&self.registered_stake, weight_table)?;
+
+        Ok(highest.map(|(root, root_stake)| RootConsensus {
+            root,
+            root_stake,
+            total_stake,
+            registered_stake,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold() -> Weight {
+        Weight::new(66, 100).unwrap()
+    }
+
+    fn root(byte: u8) -> MerkleRoot {
+        MerkleRoot { root: [byte; 32] }
+    }
+
+    fn stake(entries: &[(Pubkey, u64)]) -> TokenTable {
+        let mut table = TokenTable::new();
+        for (mint, value) in entries {
+            table.set_value(mint, *value).unwrap();
+        }
+        table
+    }
+
+    #[test]
+    fn test_is_voting_done() {
+        // Epoch 3 covers slots 300..=399
+        let merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 3, 300, 100);
+        assert_eq!(merkle_root.epoch_last_slot(), 399);
+
+        // Voting stays open during the epoch and for 50 slots after its last slot
+        assert!(!merkle_root.is_voting_done(300, 50));
+        assert!(!merkle_root.is_voting_done(399, 50));
+        assert!(!merkle_root.is_voting_done(400, 50));
+        assert!(!merkle_root.is_voting_done(449, 50));
+        assert!(merkle_root.is_voting_done(450, 50));
+        assert!(merkle_root.is_voting_done(u64::MAX, 50));
+
+        // Without a voting window, voting ends with the epoch
+        assert!(!merkle_root.is_voting_done(399, 0));
+        assert!(merkle_root.is_voting_done(400, 0));
+
+        // A window longer than an epoch runs into the following epochs
+        assert!(!merkle_root.is_voting_done(649, 250));
+        assert!(merkle_root.is_voting_done(650, 250));
+    }
+
+    #[test]
+    fn test_is_voting_done_at_slot_boundaries() {
+        // The first epoch starts at slot zero
+        let merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        assert_eq!(merkle_root.epoch_last_slot(), 99);
+        assert!(!merkle_root.is_voting_done(0, 10));
+        assert!(!merkle_root.is_voting_done(109, 10));
+        assert!(merkle_root.is_voting_done(110, 10));
+
+        // Single slot epochs
+        let merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 7, 7, 1);
+        assert_eq!(merkle_root.epoch_last_slot(), 7);
+        assert!(!merkle_root.is_voting_done(8, 1));
+        assert!(merkle_root.is_voting_done(9, 1));
+
+        // A window reaching past u64::MAX never closes
+        let start = u64::MAX - 99;
+        let merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 1, start, 100);
+        assert_eq!(merkle_root.epoch_last_slot(), u64::MAX);
+        assert!(!merkle_root.is_voting_done(u64::MAX, 0));
+        assert!(!merkle_root.is_voting_done(u64::MAX, 10));
+    }
+
+    #[test]
+    fn test_add_and_remove_vote() {
+        let mint = Pubkey::new_unique();
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        assert!(!merkle_root.has_votes());
+
+        merkle_root
+            .add_vote(&root(1), &stake(&[(mint, 10)]))
+            .unwrap();
+        assert!(merkle_root.has_votes());
+        merkle_root
+            .add_vote(&root(2), &stake(&[(mint, 5)]))
+            .unwrap();
+        merkle_root
+            .add_vote(&root(1), &stake(&[(mint, 7)]))
+            .unwrap();
+
+        assert_eq!(merkle_root.roots[0].root, root(1));
+        assert_eq!(merkle_root.roots[0].stake.find_value(&mint), Some(17));
+        assert_eq!(merkle_root.roots[1].root, root(2));
+        assert!(merkle_root.roots[2].is_empty());
+
+        // Changing a vote moves the stake to the new root
+        merkle_root
+            .remove_vote(&root(1), &stake(&[(mint, 7)]))
+            .unwrap();
+        merkle_root
+            .add_vote(&root(2), &stake(&[(mint, 7)]))
+            .unwrap();
+        assert_eq!(merkle_root.roots[0].stake.find_value(&mint), Some(10));
+        assert_eq!(merkle_root.roots[1].stake.find_value(&mint), Some(12));
+
+        assert_eq!(
+            merkle_root.remove_vote(&root(3), &stake(&[(mint, 1)])),
+            Err(RewardCoreError::RootNotFound)
+        );
+        assert_eq!(
+            merkle_root.remove_vote(&root(2), &stake(&[(mint, 13)])),
+            Err(RewardCoreError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_add_vote_no_more_root_slots() {
+        let mint = Pubkey::new_unique();
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+
+        for i in 0..EpochRewardMerkleRoot::MAX_ROOTS {
+            merkle_root
+                .add_vote(&root(i as u8 + 1), &stake(&[(mint, 1)]))
+                .unwrap();
+        }
+
+        assert_eq!(
+            merkle_root.add_vote(&root(u8::MAX), &stake(&[(mint, 1)])),
+            Err(RewardCoreError::NoMoreRootSlots)
+        );
+    }
+
+    #[test]
+    fn test_record_distribution() {
+        let mint1 = Pubkey::new_unique();
+        let mint2 = Pubkey::new_unique();
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        merkle_root
+            .deposited_rewards
+            .set_value(&mint1, 100)
+            .unwrap();
+        merkle_root.deposited_rewards.set_value(&mint2, 10).unwrap();
+
+        assert_eq!(
+            merkle_root.record_distribution(&mint1, 60, 1),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        merkle_root.open_voting(100).unwrap();
+        merkle_root
+            .finalize_consensus(
+                Some(RootConsensus {
+                    root: root(1),
+                    root_stake: 10,
+                    total_stake: 10,
+                    registered_stake: 10,
+                }),
+                &threshold(),
+                &Weight::default(),
+            )
+            .unwrap();
+
+        merkle_root.record_distribution(&mint1, 60, 1).unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Distributing));
+        merkle_root.record_distribution(&mint1, 40, 2).unwrap();
+        assert_eq!(
+            merkle_root.distributed_rewards.find_value(&mint1),
+            Some(100)
+        );
+        assert_eq!(u64::from(merkle_root.reward_payout_count), 2);
+        assert!(!merkle_root.distribution_finished());
+
+        assert_eq!(
+            merkle_root.record_distribution(&mint1, 1, 3),
+            Err(RewardCoreError::InsufficientRewards)
+        );
+        assert_eq!(
+            merkle_root.record_distribution(&Pubkey::new_unique(), 1, 3),
+            Err(RewardCoreError::InsufficientRewards)
+        );
+        assert_eq!(u64::from(merkle_root.reward_payout_count), 2);
+
+        merkle_root.record_distribution(&mint2, 10, 4).unwrap();
+        assert!(merkle_root.distribution_finished());
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Distributed));
+        assert_eq!(merkle_root.slot_distribution_finished(), 4);
+        assert_eq!(
+            merkle_root.record_distribution(&mint2, 0, 5),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+    }
+
+    fn finalized_with_consensus(deposits: &[(Pubkey, u64)]) -> EpochRewardMerkleRoot {
+        // Epoch 0 covers slots 0..=99
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        for (mint, amount) in deposits {
+            merkle_root
+                .deposited_rewards
+                .set_value(mint, *amount)
+                .unwrap();
+        }
+        merkle_root.open_voting(100).unwrap();
+        merkle_root
+            .finalize_consensus(
+                Some(RootConsensus {
+                    root: root(1),
+                    root_stake: 10,
+                    total_stake: 10,
+                    registered_stake: 10,
+                }),
+                &threshold(),
+                &Weight::default(),
+            )
+            .unwrap();
+        merkle_root
+    }
+
+    #[test]
+    fn test_finish_distribution_after_claim_window() {
+        let mint = Pubkey::new_unique();
+        let mut merkle_root = finalized_with_consensus(&[(mint, 100)]);
+        merkle_root.record_distribution(&mint, 99, 120).unwrap();
+
+        // Voting closes after slot 109 and claims after slot 159
+        assert!(!merkle_root.can_finish_distribution(159, 10, 50));
+        assert!(merkle_root.can_finish_distribution(160, 10, 50));
+        assert!(!merkle_root.can_finish_distribution(u64::MAX, u64::MAX, 50));
+
+        merkle_root.finish_distribution(160).unwrap();
+        assert!(merkle_root.distribution_finished());
+        assert_eq!(merkle_root.slot_distribution_finished(), 160);
+        assert!(!merkle_root.can_finish_distribution(200, 10, 50));
+        assert_eq!(
+            merkle_root.finish_distribution(200),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        assert_eq!(
+            merkle_root.record_distribution(&mint, 1, 200),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+    }
+
+    #[test]
+    fn test_finish_distribution_without_deposits() {
+        let mut merkle_root = finalized_with_consensus(&[]);
+
+        // Nothing to distribute, so there is no need to wait for the claim window
+        assert!(merkle_root.can_finish_distribution(100, 10, 50));
+        merkle_root.finish_distribution(100).unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Distributed));
+    }
+
+    #[test]
+    fn test_finish_distribution_requires_consensus() {
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        assert!(!merkle_root.can_finish_distribution(1_000, 10, 50));
+
+        merkle_root.open_voting(100).unwrap();
+        merkle_root
+            .finalize_consensus(None, &threshold(), &Weight::default())
+            .unwrap();
+
+        // An epoch without consensus is rolled over instead
+        assert!(!merkle_root.can_finish_distribution(1_000, 10, 50));
+        assert_eq!(
+            merkle_root.finish_distribution(1_000),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+    }
+
+    #[test]
+    fn test_needs_weight_table_without_deposits() {
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        assert!(merkle_root.needs_weight_table());
+        merkle_root.open_voting(100).unwrap();
+        assert!(merkle_root.needs_weight_table());
+
+        // Nothing was deposited, so the epoch never distributes or rolls over
+        let consensus = finalized_with_consensus(&[]);
+        assert_eq!(consensus.state(), Ok(EpochRewardState::Finalized));
+        assert!(!consensus.needs_weight_table());
+
+        merkle_root
+            .finalize_consensus(None, &threshold(), &Weight::default())
+            .unwrap();
+        assert!(!merkle_root.needs_weight_table());
+    }
+
+    #[test]
+    fn test_state_transitions() {
+        use EpochRewardState::*;
+
+        let states = [
+            AcceptingDeposits,
+            Voting,
+            Finalized,
+            Distributing,
+            Distributed,
+            RolledOver,
+        ];
+        let allowed = [
+            (AcceptingDeposits, Voting),
+            (Voting, Finalized),
+            (Finalized, Distributing),
+            (Finalized, RolledOver),
+            (Finalized, Distributed),
+            (Distributing, Distributed),
+        ];
+
+        for from in states {
+            for to in states {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    allowed.contains(&(from, to)),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+            assert_eq!(EpochRewardState::try_from(from as u8), Ok(from));
+        }
+        assert_eq!(
+            EpochRewardState::try_from(6),
+            Err(RewardCoreError::InvalidState)
+        );
+
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        assert_eq!(merkle_root.state(), Ok(AcceptingDeposits));
+        assert_eq!(
+            merkle_root.transition(Finalized),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        assert_eq!(merkle_root.state(), Ok(AcceptingDeposits));
+
+        merkle_root.transition(Voting).unwrap();
+        merkle_root.transition(Finalized).unwrap();
+
+        // Without a consensus root the epoch can only roll over
+        assert_eq!(
+            merkle_root.transition(Distributing),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        assert_eq!(
+            merkle_root.transition(Distributed),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        merkle_root.transition(RolledOver).unwrap();
+        assert!(merkle_root.rolled_over());
+        assert_eq!(
+            merkle_root.transition(Voting),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+    }
+
+    #[test]
+    fn test_open_voting() {
+        // Epoch 3 covers slots 300..=399
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 3, 300, 100);
+
+        merkle_root.open_voting(399).unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::AcceptingDeposits));
+
+        merkle_root.open_voting(400).unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Voting));
+
+        // Later states are left alone
+        merkle_root.open_voting(500).unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Voting));
+        merkle_root
+            .finalize_consensus(None, &threshold(), &Weight::default())
+            .unwrap();
+        merkle_root.open_voting(500).unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Finalized));
+    }
+
+    #[test]
+    fn test_finalize_consensus() {
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        let consensus = RootConsensus {
+            root: root(1),
+            root_stake: 70,
+            total_stake: 100,
+            registered_stake: 100,
+        };
+
+        // Only a voting epoch can be finalized
+        assert_eq!(
+            merkle_root.finalize_consensus(Some(consensus), &threshold(), &Weight::default()),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        assert_eq!(merkle_root.consensus(), None);
+
+        merkle_root.open_voting(100).unwrap();
+        merkle_root
+            .finalize_consensus(Some(consensus), &threshold(), &Weight::default())
+            .unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Finalized));
+        assert_eq!(merkle_root.consensus(), Some(consensus));
+        assert_eq!(merkle_root.voted_stake(), 100);
+        assert_eq!(merkle_root.consensus_stake(), 70);
+
+        assert_eq!(
+            merkle_root.finalize_consensus(Some(consensus), &threshold(), &Weight::default()),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+        assert_eq!(
+            merkle_root.transition(EpochRewardState::RolledOver),
+            Err(RewardCoreError::InvalidStateTransition)
+        );
+    }
+
+    #[test]
+    fn test_small_voter_cannot_finalize_early() {
+        let mint = Pubkey::new_unique();
+        let mut weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        weight_table
+            .set_weight(&mint, Weight::new(1, 1).unwrap())
+            .unwrap();
+
+        // Epoch 0 covers slots 0..=99, voting is open until slot 109
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        merkle_root.register_stake(&mint, 10).unwrap();
+        merkle_root.register_stake(&mint, 90).unwrap();
+        merkle_root.open_voting(100).unwrap();
+
+        // The first voter agrees with itself, but holds 10% of the registered stake
+        merkle_root
+            .add_vote(&root(1), &stake(&[(mint, 10)]))
+            .unwrap();
+        let tally = merkle_root.get_highest_voted_root(&weight_table).unwrap();
+        assert_eq!(tally.unwrap().root_stake, tally.unwrap().total_stake);
+        assert!(!merkle_root.can_finalize(
+            tally.as_ref(),
+            101,
+            10,
+            &threshold(),
+            &Weight::default()
+        ));
+
+        // Once the window is over the epoch finalizes, without consensus
+        assert!(merkle_root.can_finalize(
+            tally.as_ref(),
+            110,
+            10,
+            &threshold(),
+            &Weight::default()
+        ));
+
+        // The larger operator joining the same root reaches consensus early
+        merkle_root
+            .add_vote(&root(1), &stake(&[(mint, 90)]))
+            .unwrap();
+        let tally = merkle_root.get_highest_voted_root(&weight_table).unwrap();
+        assert!(merkle_root.can_finalize(
+            tally.as_ref(),
+            101,
+            10,
+            &threshold(),
+            &Weight::default()
+        ));
+
+        merkle_root
+            .finalize_consensus(tally, &threshold(), &Weight::default())
+            .unwrap();
+        assert!(!merkle_root.can_finalize(
+            tally.as_ref(),
+            110,
+            10,
+            &threshold(),
+            &Weight::default()
+        ));
+    }
+
+    #[test]
+    fn test_finalize_consensus_below_threshold() {
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        merkle_root.open_voting(100).unwrap();
+
+        // The leading root is recorded as no consensus
+        merkle_root
+            .finalize_consensus(
+                Some(RootConsensus {
+                    root: root(1),
+                    root_stake: 60,
+                    total_stake: 100,
+                    registered_stake: 100,
+                }),
+                &threshold(),
+                &Weight::default(),
+            )
+            .unwrap();
+        assert_eq!(merkle_root.state(), Ok(EpochRewardState::Finalized));
+        assert_eq!(merkle_root.consensus(), None);
+        assert!(merkle_root.consensus_root.is_empty());
+        assert_eq!(merkle_root.voted_stake(), 100);
+        assert_eq!(merkle_root.consensus_stake(), 0);
+
+        merkle_root
+            .transition(EpochRewardState::RolledOver)
+            .unwrap();
+        assert_eq!(merkle_root.consensus(), None);
+    }
+
+    #[test]
+    fn test_get_highest_voted_root_no_votes() {
+        let merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        let weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+
+        assert_eq!(merkle_root.get_highest_voted_root(&weight_table), Ok(None));
+    }
+
+    #[test]
+    fn test_get_highest_voted_root_normalizes_stake() {
+        let mint1 = Pubkey::new_unique();
+        let mint2 = Pubkey::new_unique();
+
+        let mut weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        weight_table
+            .set_weight(&mint1, Weight::new(1, 1).unwrap())
             .unwrap();
+        weight_table
+            .set_weight(&mint2, Weight::new(3, 1).unwrap())
+            .unwrap();
+
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        // More raw tokens, less normalized stake
+        merkle_root.roots[0] = MerkleRootEntry {
+            root: root(1),
+            stake: stake(&[(mint1, 200)]),
+        };
+        merkle_root.roots[1] = MerkleRootEntry {
+            root: root(2),
+            stake: stake(&[(mint2, 100)]),
+        };
 
-        voting_cutoff <= current_slot
+        let consensus = merkle_root
+            .get_highest_voted_root(&weight_table)
+            .unwrap()
+            .unwrap();
+        assert_eq!(consensus.root, root(2));
+        assert_eq!(consensus.root_stake, 300);
+        assert_eq!(consensus.total_stake, 500);
+        assert!(!consensus.consensus_reached(&threshold(), &Weight::default()));
     }
 
-    pub fn get_highest_voted_root(&self, price_table: &TokenTable) -> Option<MerkleRoot> {
-        todo!()
+    #[test]
+    fn test_get_highest_voted_root_ignores_unweighted_mints() {
+        let mint = Pubkey::new_unique();
+
+        let mut weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        weight_table
+            .set_weight(&mint, Weight::new(1, 2).unwrap())
+            .unwrap();
+
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        merkle_root.roots[0] = MerkleRootEntry {
+            root: root(1),
+            stake: stake(&[(mint, 101), (Pubkey::new_unique(), 1_000)]),
+        };
+
+        merkle_root.register_stake(&mint, 101).unwrap();
+
+        let consensus = merkle_root
+            .get_highest_voted_root(&weight_table)
+            .unwrap()
+            .unwrap();
+        // 101 / 2 rounds down
+        assert_eq!(consensus.root_stake, 50);
+        assert_eq!(consensus.total_stake, 50);
+        assert_eq!(consensus.registered_stake, 50);
+        assert!(consensus.consensus_reached(&threshold(), &Weight::default()));
+    }
+
+    #[test]
+    fn test_consensus_threshold() {
+        let leading = RootConsensus {
+            root: root(1),
+            root_stake: 65,
+            total_stake: 100,
+            registered_stake: 100,
+        };
+        assert!(!leading.consensus_reached(&threshold(), &Weight::default()));
+
+        let finalized = RootConsensus {
+            root: root(1),
+            root_stake: 66,
+            total_stake: 100,
+            registered_stake: 100,
+        };
+        assert!(finalized.consensus_reached(&threshold(), &Weight::default()));
+
+        let no_stake = RootConsensus {
+            root: root(1),
+            root_stake: 0,
+            total_stake: 0,
+            registered_stake: 0,
+        };
+        assert!(!no_stake.consensus_reached(&threshold(), &Weight::default()));
+    }
+
+    #[test]
+    fn test_consensus_min_participation() {
+        let consensus = RootConsensus {
+            root: root(1),
+            root_stake: 70,
+            total_stake: 70,
+            registered_stake: 100,
+        };
+
+        assert!(consensus.consensus_reached(&threshold(), &Weight::default()));
+        assert!(consensus.consensus_reached(&threshold(), &Weight::new(7, 10).unwrap()));
+        assert!(!consensus.consensus_reached(&threshold(), &Weight::new(3, 4).unwrap()));
+    }
+
+    #[test]
+    fn test_consensus_threshold_uses_registered_stake() {
+        // Every vote agrees, but the stake that did not vote counts against the root
+        let unanimous = RootConsensus {
+            root: root(1),
+            root_stake: 40,
+            total_stake: 40,
+            registered_stake: 100,
+        };
+        assert!(!unanimous.consensus_reached(&threshold(), &Weight::default()));
+
+        let majority = Weight::new(1, 2).unwrap();
+        let registered_majority = RootConsensus {
+            root: root(1),
+            root_stake: 50,
+            total_stake: 60,
+            registered_stake: 100,
+        };
+        assert!(registered_majority.consensus_reached(&majority, &Weight::default()));
+        assert!(!registered_majority.consensus_reached(&threshold(), &Weight::default()));
+
+        let no_registered_stake = RootConsensus {
+            root: root(1),
+            root_stake: 50,
+            total_stake: 50,
+            registered_stake: 0,
+        };
+        assert!(!no_registered_stake.consensus_reached(&majority, &Weight::default()));
+    }
+
+    #[test]
+    fn test_register_stake() {
+        let mint = Pubkey::new_unique();
+        let mut weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        weight_table
+            .set_weight(&mint, Weight::new(2, 1).unwrap())
+            .unwrap();
+
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        merkle_root.register_stake(&mint, 30).unwrap();
+        merkle_root.register_stake(&mint, 70).unwrap();
+        merkle_root.open_voting(100).unwrap();
+        assert_eq!(
+            merkle_root.register_stake(&mint, 1),
+            Err(RewardCoreError::InvalidState)
+        );
+        merkle_root
+            .add_vote(&root(1), &stake(&[(mint, 30)]))
+            .unwrap();
+
+        let tally = merkle_root
+            .get_highest_voted_root(&weight_table)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tally.total_stake, 60);
+        assert_eq!(tally.registered_stake, 200);
+
+        // 30% turnout misses a 1/2 minimum participation even though every vote agrees
+        merkle_root
+            .finalize_consensus(Some(tally), &threshold(), &Weight::new(1, 2).unwrap())
+            .unwrap();
+        assert_eq!(merkle_root.consensus(), None);
+        assert_eq!(merkle_root.voted_stake(), 60);
+        assert_eq!(merkle_root.registered_normalized_stake(), 200);
+
+        assert_eq!(
+            merkle_root.register_stake(&mint, 1),
+            Err(RewardCoreError::InvalidState)
+        );
+    }
+
+    #[test]
+    fn test_get_highest_voted_root_tie_goes_to_first() {
+        let mint = Pubkey::new_unique();
+
+        let mut weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        weight_table
+            .set_weight(&mint, Weight::new(1, 1).unwrap())
+            .unwrap();
+
+        let mut merkle_root = EpochRewardMerkleRoot::new(Pubkey::new_unique(), 0, 0, 100);
+        merkle_root.roots[0] = MerkleRootEntry {
+            root: root(1),
+            stake: stake(&[(mint, 10)]),
+        };
+        merkle_root.roots[1] = MerkleRootEntry {
+            root: root(2),
+            stake: stake(&[(mint, 10)]),
+        };
+
+        let consensus = merkle_root
+            .get_highest_voted_root(&weight_table)
+            .unwrap()
+            .unwrap();
+        assert_eq!(consensus.root, root(1));
+        assert_eq!(consensus.total_stake, 20);
+    }
+
+    #[test]
+    fn test_snapshot_config() {
+        let mut config = RewardConfig::new(&Pubkey::new_unique(), &Pubkey::new_unique(), 10, 20, 1);
+        let mut merkle_root = EpochRewardMerkleRoot::new(config.ncn, 0, 0, 100);
+        merkle_root.snapshot_config(&config);
+
+        config.set_valid_voting_slots(30).unwrap();
+        config.set_slots_before_closing_marker_accounts(40).unwrap();
+        config.set_claim_window_slots(50).unwrap();
+        config.fee_wallet = Pubkey::new_unique();
+        config.set_fee_bps(500).unwrap();
+        config
+            .set_consensus_threshold(Weight::new(9, 10).unwrap())
+            .unwrap();
+        config
+            .set_min_participation(Weight::new(1, 2).unwrap())
+            .unwrap();
+
+        assert_eq!(merkle_root.valid_voting_slots(), 10);
+        assert_eq!(merkle_root.slots_before_closing_marker_accounts(), 20);
+        assert_eq!(
+            merkle_root.claim_window_slots(),
+            RewardConfig::DEFAULT_CLAIM_WINDOW_SLOTS
+        );
+        assert_eq!(merkle_root.consensus_threshold(), threshold());
+        assert!(merkle_root.min_participation().is_zero());
+        assert_eq!(merkle_root.fee_wallet(), Pubkey::default());
+        assert_eq!(merkle_root.fee_bps(), 0);
+    }
+
+    #[test]
+    fn test_is_stake_snapshot_open() {
+        let mut config = RewardConfig::new(&Pubkey::new_unique(), &Pubkey::new_unique(), 10, 20, 1);
+        config.set_stake_snapshot_slots(10).unwrap();
+
+        // Epoch 3 covers slots 300..=399, stake can be snapshot in 300..=309
+        let mut merkle_root = EpochRewardMerkleRoot::new(config.ncn, 3, 300, 100);
+        merkle_root.snapshot_config(&config);
+        assert!(!merkle_root.is_stake_snapshot_open(299));
+        assert!(merkle_root.is_stake_snapshot_open(300));
+        assert!(merkle_root.is_stake_snapshot_open(309));
+        assert!(!merkle_root.is_stake_snapshot_open(310));
+
+        // The window never extends past the epoch
+        config.set_stake_snapshot_slots(1_000).unwrap();
+        merkle_root.snapshot_config(&config);
+        assert!(merkle_root.is_stake_snapshot_open(399));
+        assert!(!merkle_root.is_stake_snapshot_open(400));
     }
 }
@@ -1,7 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 use jito_bytemuck::{AccountDeserialize, Discriminator};
 use shank::ShankAccount;
-use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::{discriminators::Discriminators, merkle_root::MerkleRoot, token_table::StakeTable};
 
@@ -11,7 +11,7 @@ use crate::{discriminators::Discriminators, merkle_root::MerkleRoot, token_table
 pub struct EpochRewardMerkleRootTicket {
     pub ncn: Pubkey,
     pub operator: Pubkey,
-    pub ncn_slot: u64,
+    pub ncn_epoch: u64,
     pub root: MerkleRoot,
     pub stake_table: StakeTable,
 }
@@ -21,14 +21,20 @@ impl Discriminator for EpochRewardMerkleRootTicket {
 }
 
 impl EpochRewardMerkleRootTicket {
-    /// Create a new vault account
-    pub fn new(ncn: Pubkey, operator: Pubkey, ncn_slot: u64) -> Self {
+    pub fn size() -> u64 {
+        8_u64
+            .checked_add(std::mem::size_of::<EpochRewardMerkleRootTicket>() as u64)
+            .unwrap()
+    }
+
+    /// Create a new ticket seeded with the operator's verified stake
+    pub fn new(ncn: Pubkey, operator: Pubkey, ncn_epoch: u64, stake_table: StakeTable) -> Self {
         Self {
             ncn,
             operator,
-            ncn_slot,
+            ncn_epoch,
             root: MerkleRoot::default(),
-            stake_table: StakeTable::default(),
+            stake_table,
         }
     }
 
@@ -55,6 +61,40 @@ impl EpochRewardMerkleRootTicket {
         (pda, bump, seeds)
     }
 
+    pub fn load(
+        program_id: &Pubkey,
+        ncn: &Pubkey,
+        operator: &Pubkey,
+        ncn_epoch: u64,
+        account: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<(), ProgramError> {
+        if account.owner.ne(program_id) {
+            msg!("Epoch reward merkle root ticket account has an invalid owner");
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if account.data_is_empty() {
+            msg!("Epoch reward merkle root ticket account data is empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if expect_writable && !account.is_writable {
+            msg!("Epoch reward merkle root ticket account is not writable");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account.data.borrow()[0].ne(&Self::DISCRIMINATOR) {
+            msg!("Epoch reward merkle root ticket account discriminator is invalid");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account
+            .key
+            .ne(&Self::find_program_address(program_id, ncn, operator, ncn_epoch).0)
+        {
+            msg!("Epoch reward merkle root ticket account is not at the correct PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
     pub fn has_voted(&self) -> bool {
         !self.root.is_empty()
     }
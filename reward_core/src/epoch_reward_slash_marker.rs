@@ -0,0 +1,63 @@
+use bytemuck::{Pod, Zeroable};
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+
+use crate::discriminators::Discriminators;
+
+/// Marks that an operator's delegation from a vault has been slashed for an epoch,
+/// so the same delegation cannot be slashed twice for one epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable, AccountDeserialize, ShankAccount)]
+#[repr(C)]
+pub struct EpochRewardSlashMarker {
+    pub epoch_reward_merkle_root: Pubkey,
+    pub operator: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl Discriminator for EpochRewardSlashMarker {
+    const DISCRIMINATOR: u8 = Discriminators::EpochRewardSlashMarker as u8;
+}
+
+impl EpochRewardSlashMarker {
+    pub fn size() -> u64 {
+        8_u64
+            .checked_add(std::mem::size_of::<EpochRewardSlashMarker>() as u64)
+            .unwrap()
+    }
+
+    pub fn new(epoch_reward_merkle_root: Pubkey, operator: Pubkey, vault: Pubkey) -> Self {
+        Self {
+            epoch_reward_merkle_root,
+            operator,
+            vault,
+        }
+    }
+
+    /// Returns the seeds for the PDA
+    pub fn seeds(
+        epoch_reward_merkle_root: &Pubkey,
+        operator: &Pubkey,
+        vault: &Pubkey,
+    ) -> Vec<Vec<u8>> {
+        vec![
+            b"EPOCH_REWARD_SLASH_MARKER".as_ref().to_vec(),
+            epoch_reward_merkle_root.to_bytes().to_vec(),
+            operator.to_bytes().to_vec(),
+            vault.to_bytes().to_vec(),
+        ]
+    }
+
+    /// Returns the PDA
+    pub fn find_program_address(
+        program_id: &Pubkey,
+        epoch_reward_merkle_root: &Pubkey,
+        operator: &Pubkey,
+        vault: &Pubkey,
+    ) -> (Pubkey, u8, Vec<Vec<u8>>) {
+        let seeds = Self::seeds(epoch_reward_merkle_root, operator, vault);
+        let seeds_iter: Vec<_> = seeds.iter().map(|s| s.as_slice()).collect();
+        let (pda, bump) = Pubkey::find_program_address(&seeds_iter, program_id);
+        (pda, bump, seeds)
+    }
+}
@@ -0,0 +1,55 @@
+use bytemuck::{Pod, Zeroable};
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+
+use crate::discriminators::Discriminators;
+
+/// Marks that a vault's delegation has been counted in an operator's ticket,
+/// so the same vault cannot be added twice in one epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable, AccountDeserialize, ShankAccount)]
+#[repr(C)]
+pub struct EpochRewardTicketVaultMarker {
+    pub epoch_reward_merkle_root_ticket: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl Discriminator for EpochRewardTicketVaultMarker {
+    const DISCRIMINATOR: u8 = Discriminators::EpochRewardTicketVaultMarker as u8;
+}
+
+impl EpochRewardTicketVaultMarker {
+    pub fn size() -> u64 {
+        8_u64
+            .checked_add(std::mem::size_of::<EpochRewardTicketVaultMarker>() as u64)
+            .unwrap()
+    }
+
+    pub fn new(epoch_reward_merkle_root_ticket: Pubkey, vault: Pubkey) -> Self {
+        Self {
+            epoch_reward_merkle_root_ticket,
+            vault,
+        }
+    }
+
+    /// Returns the seeds for the PDA
+    pub fn seeds(epoch_reward_merkle_root_ticket: &Pubkey, vault: &Pubkey) -> Vec<Vec<u8>> {
+        vec![
+            b"EPOCH_REWARD_TICKET_VAULT_MARKER".as_ref().to_vec(),
+            epoch_reward_merkle_root_ticket.to_bytes().to_vec(),
+            vault.to_bytes().to_vec(),
+        ]
+    }
+
+    /// Returns the PDA
+    pub fn find_program_address(
+        program_id: &Pubkey,
+        epoch_reward_merkle_root_ticket: &Pubkey,
+        vault: &Pubkey,
+    ) -> (Pubkey, u8, Vec<Vec<u8>>) {
+        let seeds = Self::seeds(epoch_reward_merkle_root_ticket, vault);
+        let seeds_iter: Vec<_> = seeds.iter().map(|s| s.as_slice()).collect();
+        let (pda, bump) = Pubkey::find_program_address(&seeds_iter, program_id);
+        (pda, bump, seeds)
+    }
+}
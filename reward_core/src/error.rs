@@ -5,6 +5,36 @@ use thiserror::Error;
 pub enum RewardCoreError {
     #[error("No more table slots available")]
     NoMoreTableSlots = 0x2000,
+    #[error("No more root slots available")]
+    NoMoreRootSlots = 0x2001,
+    #[error("Root not found")]
+    RootNotFound = 0x2002,
+    #[error("Not enough rewards deposited")]
+    InsufficientRewards = 0x2003,
+    #[error("Invalid epoch reward state")]
+    InvalidState = 0x2004,
+    #[error("Invalid epoch reward state transition")]
+    InvalidStateTransition = 0x2005,
+    #[error("Invalid valid voting slots")]
+    InvalidValidVotingSlots = 0x2006,
+    #[error("Invalid consensus threshold")]
+    InvalidConsensusThreshold = 0x2007,
+    #[error("Invalid minimum participation")]
+    InvalidMinParticipation = 0x2008,
+    #[error("Invalid slots before closing marker accounts")]
+    InvalidSlotsBeforeClosingMarkerAccounts = 0x2009,
+    #[error("Invalid stake snapshot slots")]
+    InvalidStakeSnapshotSlots = 0x200A,
+    #[error("Invalid slash amount")]
+    InvalidSlashAmount = 0x200B,
+    #[error("Invalid slashable epochs")]
+    InvalidSlashableEpochs = 0x200C,
+    #[error("Invalid claim window slots")]
+    InvalidClaimWindowSlots = 0x200D,
+    #[error("Invalid fee bps")]
+    InvalidFeeBps = 0x200E,
+    #[error("Overflow")]
+    ArithmeticOverflow = 0x2100,
 }
 
 impl<T> DecodeError<T> for RewardCoreError {
@@ -3,8 +3,11 @@ pub mod epoch_reward_distribution_marker;
 pub mod epoch_reward_merkle_root;
 pub mod epoch_reward_merkle_root_ticket;
 pub mod epoch_reward_price_table;
+pub mod epoch_reward_slash_marker;
+pub mod epoch_reward_ticket_vault_marker;
 pub mod error;
 pub mod merkle_root;
+pub mod merkle_tree;
 pub mod reward_config;
 pub mod reward_dropbox;
 pub mod token_table;
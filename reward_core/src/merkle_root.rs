@@ -28,6 +28,6 @@ impl MerkleRoot {
             }
         }
 
-        return true;
+        true
     }
 }
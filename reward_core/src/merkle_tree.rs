@@ -0,0 +1,81 @@
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+use crate::merkle_root::MerkleRoot;
+
+/// Domain separators so a leaf can never be passed off as an intermediate node
+pub const LEAF_PREFIX: &[u8] = &[0];
+pub const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+/// Hashes a single payout of `amount` of `mint` to `recipient`.
+/// Native SOL payouts use the native mint.
+pub fn hash_leaf(recipient: &Pubkey, mint: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        recipient.as_ref(),
+        mint.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Hashes two nodes into their parent. The pair is sorted first, so proofs
+/// do not need to record which side each sibling is on.
+pub fn hash_intermediate(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[INTERMEDIATE_PREFIX, left, right]).to_bytes()
+}
+
+/// Checks that `leaf` is part of the tree with `root`
+pub fn verify_proof(proof: &[[u8; 32]], root: &MerkleRoot, leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_intermediate(&node, sibling));
+
+    computed == root.root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_proof() {
+        let mint = Pubkey::new_unique();
+        let leaves: Vec<[u8; 32]> = (0..3)
+            .map(|i| hash_leaf(&Pubkey::new_unique(), &mint, 100 + i))
+            .collect();
+
+        // An odd node is carried up to the next level unchanged
+        let left = hash_intermediate(&leaves[0], &leaves[1]);
+        let root = MerkleRoot {
+            root: hash_intermediate(&left, &leaves[2]),
+        };
+
+        assert!(verify_proof(&[leaves[1], leaves[2]], &root, leaves[0]));
+        assert!(verify_proof(&[leaves[0], leaves[2]], &root, leaves[1]));
+        assert!(verify_proof(&[left], &root, leaves[2]));
+
+        assert!(!verify_proof(&[leaves[2]], &root, leaves[0]));
+        assert!(!verify_proof(&[leaves[1], leaves[2]], &root, leaves[2]));
+        assert!(!verify_proof(&[], &root, leaves[0]));
+    }
+
+    #[test]
+    fn test_leaf_is_not_intermediate() {
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        assert_ne!(
+            hash_leaf(&recipient, &mint, 1),
+            hash_leaf(&recipient, &mint, 2)
+        );
+        assert_ne!(
+            hash_leaf(&recipient, &mint, 1),
+            hash_leaf(&mint, &recipient, 1)
+        );
+
+        let a = hash_leaf(&recipient, &mint, 1);
+        let b = hash_leaf(&mint, &recipient, 1);
+        assert_eq!(hash_intermediate(&a, &b), hash_intermediate(&b, &a));
+    }
+}
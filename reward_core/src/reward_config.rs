@@ -1,9 +1,13 @@
 use bytemuck::{Pod, Zeroable};
-use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use jito_bytemuck::{
+    types::{PodBool, PodU64},
+    AccountDeserialize, Discriminator,
+};
+use jito_weight_table_core::weight::Weight;
 use shank::ShankAccount;
 use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
 
-use crate::discriminators::Discriminators;
+use crate::{discriminators::Discriminators, error::RewardCoreError};
 
 /// The vault is responsible for holding tokens and minting VRT tokens
 /// based on the amount of tokens deposited.
@@ -17,6 +21,21 @@ pub struct RewardConfig {
 
     pub valid_voting_slots: PodU64, // amount of slots voting is valid for after an epoch ends
     pub slots_before_closing_marker_accounts: PodU64, // amount of slots before a marker account can be closed
+    pub slash_amount: PodU64, // amount slashed from each vault delegation of an operator that breaks the voting rules
+    consensus_threshold: Weight, // fraction of the registered stake a root needs to reach consensus
+    min_participation: Weight, // fraction of the registered ticket stake that has to vote for consensus
+    pub stake_snapshot_slots: PodU64, // slots after an epoch starts in which tickets can be created and stake added
+    pub slashable_epochs: PodU64, // epochs after an epoch in which its operators can still be slashed
+    pub claim_window_slots: PodU64, // slots after the voting window in which rewards can be claimed before the rest can be swept
+
+    pub pending_admin: Pubkey, // set by the admin, becomes the admin once it accepts. Default if no transfer is pending
+    pub pause_authority: Pubkey, // can pause and resume the program, default if unset
+    pub paused: PodBool, // blocks deposits, distribution, rollovers and early finalization while set, never slashing
+    pub fee_authority: Pubkey, // sets the reward fee, default if unset
+    pub fee_wallet: Pubkey, // receives the reward fee through the merkle root
+    fee_bps: PodU64, // share of each epoch's rewards in basis points that operators bake into the root for `fee_wallet`
+
+    reserved: [u8; 95], // new fields are carved out of the reserved space so the account size never changes
 }
 
 impl Discriminator for RewardConfig {
@@ -24,6 +43,29 @@ impl Discriminator for RewardConfig {
 }
 
 impl RewardConfig {
+    /// 66% of the registered stake, the threshold used before it was configurable
+    pub const DEFAULT_CONSENSUS_THRESHOLD: (u64, u64) = (66, 100);
+    /// Upper bound of `valid_voting_slots`, about three mainnet epochs
+    pub const MAX_VALID_VOTING_SLOTS: u64 = 3 * 432_000;
+    /// Upper bound of `slots_before_closing_marker_accounts`, about three mainnet epochs
+    pub const MAX_SLOTS_BEFORE_CLOSING_MARKER_ACCOUNTS: u64 = 3 * 432_000;
+    /// Tickets can be created and stake added during the first tenth of a mainnet epoch
+    pub const DEFAULT_STAKE_SNAPSHOT_SLOTS: u64 = 43_200;
+    /// Upper bound of `stake_snapshot_slots`, one mainnet epoch
+    pub const MAX_STAKE_SNAPSHOT_SLOTS: u64 = 432_000;
+    /// Upper bound of `slash_amount`, one million tokens with 9 decimals
+    pub const MAX_SLASH_AMOUNT: u64 = 1_000_000_000_000_000;
+    /// Covers the longest voting window of three epochs plus one epoch to slash
+    pub const DEFAULT_SLASHABLE_EPOCHS: u64 = 4;
+    /// Upper bound of `slashable_epochs`
+    pub const MAX_SLASHABLE_EPOCHS: u64 = 16;
+    /// Rewards can be claimed for one mainnet epoch after voting before they can be swept
+    pub const DEFAULT_CLAIM_WINDOW_SLOTS: u64 = 432_000;
+    /// Upper bound of `claim_window_slots`, about three mainnet epochs
+    pub const MAX_CLAIM_WINDOW_SLOTS: u64 = 3 * 432_000;
+    /// Upper bound of `fee_bps`, all of the rewards
+    pub const MAX_FEE_BPS: u64 = 10_000;
+
     pub fn size() -> u64 {
         8_u64
             .checked_add(std::mem::size_of::<RewardConfig>() as u64)
@@ -36,6 +78,7 @@ impl RewardConfig {
         admin: &Pubkey,
         valid_voting_slots: u64,
         slots_before_closing_marker_accounts: u64,
+        slash_amount: u64,
     ) -> Self {
         Self {
             ncn: *ncn,
@@ -45,7 +88,193 @@ impl RewardConfig {
             slots_before_closing_marker_accounts: PodU64::from(
                 slots_before_closing_marker_accounts,
             ),
+            slash_amount: PodU64::from(slash_amount),
+            consensus_threshold: Weight::new(
+                Self::DEFAULT_CONSENSUS_THRESHOLD.0,
+                Self::DEFAULT_CONSENSUS_THRESHOLD.1,
+            )
+            .unwrap(),
+            min_participation: Weight::default(),
+            stake_snapshot_slots: PodU64::from(Self::DEFAULT_STAKE_SNAPSHOT_SLOTS),
+            slashable_epochs: PodU64::from(Self::DEFAULT_SLASHABLE_EPOCHS),
+            claim_window_slots: PodU64::from(Self::DEFAULT_CLAIM_WINDOW_SLOTS),
+            pending_admin: Pubkey::default(),
+            pause_authority: Pubkey::default(),
+            paused: PodBool::from(false),
+            fee_authority: Pubkey::default(),
+            fee_wallet: Pubkey::default(),
+            fee_bps: PodU64::from(0),
+            reserved: [0; 95],
+        }
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused.into()
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = PodBool::from(paused);
+    }
+
+    /// Starts a transfer of the admin role, `new_admin` has to accept it before it takes
+    /// effect. A later call replaces the pending admin.
+    pub fn set_pending_admin(&mut self, new_admin: &Pubkey) {
+        self.pending_admin = *new_admin;
+    }
+
+    /// Whether `new_admin` is the pending admin and can accept the admin role
+    pub fn is_pending_admin(&self, new_admin: &Pubkey) -> bool {
+        self.pending_admin.ne(&Pubkey::default()) && self.pending_admin.eq(new_admin)
+    }
+
+    /// Completes the transfer started by `set_pending_admin`
+    pub fn accept_admin(&mut self) {
+        self.admin = self.pending_admin;
+        self.pending_admin = Pubkey::default();
+    }
+
+    /// Whether `authority` holds an optional role, an unset role matches no one
+    pub fn is_role_holder(role: &Pubkey, authority: &Pubkey) -> bool {
+        role.ne(&Pubkey::default()) && role.eq(authority)
+    }
+
+    pub fn consensus_threshold(&self) -> Weight {
+        self.consensus_threshold
+    }
+
+    pub fn fee_bps(&self) -> u64 {
+        self.fee_bps.into()
+    }
+
+    /// The fee is a share of the rewards, so at most `MAX_FEE_BPS`
+    pub fn set_fee_bps(&mut self, fee_bps: u64) -> Result<(), RewardCoreError> {
+        if fee_bps > Self::MAX_FEE_BPS {
+            return Err(RewardCoreError::InvalidFeeBps);
         }
+
+        self.fee_bps = PodU64::from(fee_bps);
+        Ok(())
+    }
+
+    pub fn min_participation(&self) -> Weight {
+        self.min_participation
+    }
+
+    /// Voting has to stay open for at least one slot after the epoch ends and at most
+    /// `MAX_VALID_VOTING_SLOTS`, so every epoch eventually leaves voting
+    pub fn set_valid_voting_slots(
+        &mut self,
+        valid_voting_slots: u64,
+    ) -> Result<(), RewardCoreError> {
+        if valid_voting_slots == 0 || valid_voting_slots > Self::MAX_VALID_VOTING_SLOTS {
+            return Err(RewardCoreError::InvalidValidVotingSlots);
+        }
+
+        self.valid_voting_slots = PodU64::from(valid_voting_slots);
+        Ok(())
+    }
+
+    /// Marker accounts can be kept for at most `MAX_SLOTS_BEFORE_CLOSING_MARKER_ACCOUNTS` after
+    /// the distribution finished
+    pub fn set_slots_before_closing_marker_accounts(
+        &mut self,
+        slots_before_closing_marker_accounts: u64,
+    ) -> Result<(), RewardCoreError> {
+        if slots_before_closing_marker_accounts > Self::MAX_SLOTS_BEFORE_CLOSING_MARKER_ACCOUNTS {
+            return Err(RewardCoreError::InvalidSlotsBeforeClosingMarkerAccounts);
+        }
+
+        self.slots_before_closing_marker_accounts =
+            PodU64::from(slots_before_closing_marker_accounts);
+        Ok(())
+    }
+
+    /// Unclaimed rewards wait at least one slot and at most `MAX_CLAIM_WINDOW_SLOTS` after the
+    /// voting window before they can be swept to a later epoch
+    pub fn set_claim_window_slots(
+        &mut self,
+        claim_window_slots: u64,
+    ) -> Result<(), RewardCoreError> {
+        if claim_window_slots == 0 || claim_window_slots > Self::MAX_CLAIM_WINDOW_SLOTS {
+            return Err(RewardCoreError::InvalidClaimWindowSlots);
+        }
+
+        self.claim_window_slots = PodU64::from(claim_window_slots);
+        Ok(())
+    }
+
+    /// Tickets read the delegated stake when they are created or updated, so the window has
+    /// to stay short for the stake to be a snapshot of the epoch start. At least one slot and
+    /// at most `MAX_STAKE_SNAPSHOT_SLOTS`.
+    pub fn set_stake_snapshot_slots(
+        &mut self,
+        stake_snapshot_slots: u64,
+    ) -> Result<(), RewardCoreError> {
+        if stake_snapshot_slots == 0 || stake_snapshot_slots > Self::MAX_STAKE_SNAPSHOT_SLOTS {
+            return Err(RewardCoreError::InvalidStakeSnapshotSlots);
+        }
+
+        self.stake_snapshot_slots = PodU64::from(stake_snapshot_slots);
+        Ok(())
+    }
+
+    /// Slashing takes `slash_amount` from each vault delegation of an operator, so it has to be
+    /// at least 1 and at most `MAX_SLASH_AMOUNT`
+    pub fn set_slash_amount(&mut self, slash_amount: u64) -> Result<(), RewardCoreError> {
+        if slash_amount == 0 || slash_amount > Self::MAX_SLASH_AMOUNT {
+            return Err(RewardCoreError::InvalidSlashAmount);
+        }
+
+        self.slash_amount = PodU64::from(slash_amount);
+        Ok(())
+    }
+
+    /// At least one and at most `MAX_SLASHABLE_EPOCHS`. An epoch is only slashed once its
+    /// voting is finalized, so this should cover `valid_voting_slots`.
+    pub fn set_slashable_epochs(&mut self, slashable_epochs: u64) -> Result<(), RewardCoreError> {
+        if slashable_epochs == 0 || slashable_epochs > Self::MAX_SLASHABLE_EPOCHS {
+            return Err(RewardCoreError::InvalidSlashableEpochs);
+        }
+
+        self.slashable_epochs = PodU64::from(slashable_epochs);
+        Ok(())
+    }
+
+    /// Whether operators can still be slashed for `ncn_epoch` during `current_epoch`
+    pub fn is_slashable_epoch(&self, ncn_epoch: u64, current_epoch: u64) -> bool {
+        current_epoch.saturating_sub(ncn_epoch) <= u64::from(self.slashable_epochs)
+    }
+
+    /// The threshold must be above 1/2 and at most 1, so two roots can never both reach it and
+    /// an even split of the registered stake never reaches consensus.
+    pub fn set_consensus_threshold(
+        &mut self,
+        consensus_threshold: Weight,
+    ) -> Result<(), RewardCoreError> {
+        let numerator = consensus_threshold.numerator() as u128;
+        let denominator = consensus_threshold.denominator() as u128;
+
+        if denominator == 0 || numerator > denominator || numerator * 2 <= denominator {
+            return Err(RewardCoreError::InvalidConsensusThreshold);
+        }
+
+        self.consensus_threshold = consensus_threshold;
+        Ok(())
+    }
+
+    /// The minimum participation must be between 0 and 1 inclusive, 0 disables the check
+    pub fn set_min_participation(
+        &mut self,
+        min_participation: Weight,
+    ) -> Result<(), RewardCoreError> {
+        if min_participation.denominator() == 0
+            || min_participation.numerator() > min_participation.denominator()
+        {
+            return Err(RewardCoreError::InvalidMinParticipation);
+        }
+
+        self.min_participation = min_participation;
+        Ok(())
     }
 
     /// Returns the seeds for the PDA
@@ -93,3 +322,284 @@ impl RewardConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RewardConfig {
+        RewardConfig::new(&Pubkey::new_unique(), &Pubkey::new_unique(), 100, 100, 1)
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = config();
+        assert!(config
+            .consensus_threshold()
+            .eq(&Weight::new(66, 100).unwrap()));
+        assert!(config.min_participation().is_zero());
+        assert_eq!(
+            u64::from(config.stake_snapshot_slots),
+            RewardConfig::DEFAULT_STAKE_SNAPSHOT_SLOTS
+        );
+        assert_eq!(
+            u64::from(config.slashable_epochs),
+            RewardConfig::DEFAULT_SLASHABLE_EPOCHS
+        );
+        assert_eq!(
+            u64::from(config.claim_window_slots),
+            RewardConfig::DEFAULT_CLAIM_WINDOW_SLOTS
+        );
+    }
+
+    #[test]
+    fn test_size() {
+        // Fields added to the config have to shrink `reserved` by their size
+        assert_eq!(RewardConfig::size(), 384);
+    }
+
+    #[test]
+    fn test_admin_transfer() {
+        let mut config = config();
+        let admin = config.admin;
+        let new_admin = Pubkey::new_unique();
+
+        assert!(!config.is_pending_admin(&new_admin));
+        assert!(!config.is_pending_admin(&Pubkey::default()));
+
+        config.set_pending_admin(&new_admin);
+        assert_eq!(config.admin, admin);
+        assert!(config.is_pending_admin(&new_admin));
+        assert!(!config.is_pending_admin(&admin));
+
+        config.accept_admin();
+        assert_eq!(config.admin, new_admin);
+        assert_eq!(config.pending_admin, Pubkey::default());
+        assert!(!config.is_pending_admin(&new_admin));
+    }
+
+    #[test]
+    fn test_optional_roles() {
+        let mut config = config();
+        let pause_authority = Pubkey::new_unique();
+
+        assert!(!RewardConfig::is_role_holder(
+            &config.pause_authority,
+            &Pubkey::default()
+        ));
+
+        config.pause_authority = pause_authority;
+        assert!(RewardConfig::is_role_holder(
+            &config.pause_authority,
+            &pause_authority
+        ));
+        assert!(!RewardConfig::is_role_holder(
+            &config.admin,
+            &pause_authority
+        ));
+
+        assert!(!config.paused());
+        config.set_paused(true);
+        assert!(config.paused());
+
+        let fee_authority = Pubkey::new_unique();
+        config.fee_authority = fee_authority;
+        assert!(RewardConfig::is_role_holder(
+            &config.fee_authority,
+            &fee_authority
+        ));
+        assert!(!RewardConfig::is_role_holder(
+            &config.pause_authority,
+            &fee_authority
+        ));
+    }
+
+    #[test]
+    fn test_set_fee_bps() {
+        let mut config = config();
+        assert_eq!(config.fee_bps(), 0);
+        assert_eq!(
+            config.set_fee_bps(RewardConfig::MAX_FEE_BPS + 1),
+            Err(RewardCoreError::InvalidFeeBps)
+        );
+        assert_eq!(config.fee_bps(), 0);
+
+        for fee_bps in [0, 500, RewardConfig::MAX_FEE_BPS] {
+            config.set_fee_bps(fee_bps).unwrap();
+            assert_eq!(config.fee_bps(), fee_bps);
+        }
+    }
+
+    #[test]
+    fn test_set_valid_voting_slots() {
+        let mut config = config();
+        assert_eq!(
+            config.set_valid_voting_slots(0),
+            Err(RewardCoreError::InvalidValidVotingSlots)
+        );
+        assert_eq!(
+            config.set_valid_voting_slots(RewardConfig::MAX_VALID_VOTING_SLOTS + 1),
+            Err(RewardCoreError::InvalidValidVotingSlots)
+        );
+        assert_eq!(
+            config.set_valid_voting_slots(u64::MAX),
+            Err(RewardCoreError::InvalidValidVotingSlots)
+        );
+        config.set_valid_voting_slots(1).unwrap();
+        assert_eq!(u64::from(config.valid_voting_slots), 1);
+        config
+            .set_valid_voting_slots(RewardConfig::MAX_VALID_VOTING_SLOTS)
+            .unwrap();
+        assert_eq!(
+            u64::from(config.valid_voting_slots),
+            RewardConfig::MAX_VALID_VOTING_SLOTS
+        );
+    }
+
+    #[test]
+    fn test_set_slots_before_closing_marker_accounts() {
+        let mut config = config();
+        assert_eq!(
+            config.set_slots_before_closing_marker_accounts(
+                RewardConfig::MAX_SLOTS_BEFORE_CLOSING_MARKER_ACCOUNTS + 1
+            ),
+            Err(RewardCoreError::InvalidSlotsBeforeClosingMarkerAccounts)
+        );
+        assert_eq!(u64::from(config.slots_before_closing_marker_accounts), 100);
+
+        for slots in [0, RewardConfig::MAX_SLOTS_BEFORE_CLOSING_MARKER_ACCOUNTS] {
+            config
+                .set_slots_before_closing_marker_accounts(slots)
+                .unwrap();
+            assert_eq!(
+                u64::from(config.slots_before_closing_marker_accounts),
+                slots
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_claim_window_slots() {
+        let mut config = config();
+        for slots in [0, RewardConfig::MAX_CLAIM_WINDOW_SLOTS + 1] {
+            assert_eq!(
+                config.set_claim_window_slots(slots),
+                Err(RewardCoreError::InvalidClaimWindowSlots)
+            );
+        }
+
+        for slots in [1, RewardConfig::MAX_CLAIM_WINDOW_SLOTS] {
+            config.set_claim_window_slots(slots).unwrap();
+            assert_eq!(u64::from(config.claim_window_slots), slots);
+        }
+    }
+
+    #[test]
+    fn test_set_stake_snapshot_slots() {
+        let mut config = config();
+        for slots in [0, RewardConfig::MAX_STAKE_SNAPSHOT_SLOTS + 1] {
+            assert_eq!(
+                config.set_stake_snapshot_slots(slots),
+                Err(RewardCoreError::InvalidStakeSnapshotSlots)
+            );
+        }
+
+        for slots in [1, RewardConfig::MAX_STAKE_SNAPSHOT_SLOTS] {
+            config.set_stake_snapshot_slots(slots).unwrap();
+            assert_eq!(u64::from(config.stake_snapshot_slots), slots);
+        }
+    }
+
+    #[test]
+    fn test_set_slash_amount() {
+        let mut config = config();
+        for amount in [0, RewardConfig::MAX_SLASH_AMOUNT + 1] {
+            assert_eq!(
+                config.set_slash_amount(amount),
+                Err(RewardCoreError::InvalidSlashAmount)
+            );
+        }
+        assert_eq!(u64::from(config.slash_amount), 1);
+
+        config
+            .set_slash_amount(RewardConfig::MAX_SLASH_AMOUNT)
+            .unwrap();
+        assert_eq!(
+            u64::from(config.slash_amount),
+            RewardConfig::MAX_SLASH_AMOUNT
+        );
+    }
+
+    #[test]
+    fn test_slashable_epochs() {
+        let mut config = config();
+        for epochs in [0, RewardConfig::MAX_SLASHABLE_EPOCHS + 1] {
+            assert_eq!(
+                config.set_slashable_epochs(epochs),
+                Err(RewardCoreError::InvalidSlashableEpochs)
+            );
+        }
+
+        config.set_slashable_epochs(2).unwrap();
+        assert!(config.is_slashable_epoch(10, 10));
+        assert!(config.is_slashable_epoch(10, 12));
+        assert!(!config.is_slashable_epoch(10, 13));
+    }
+
+    #[test]
+    fn test_set_consensus_threshold() {
+        let mut config = config();
+
+        for (numerator, denominator) in [(51, 100), (2, 3), (1, 1), (u64::MAX, u64::MAX)] {
+            let threshold = Weight::new(numerator, denominator).unwrap();
+            config.set_consensus_threshold(threshold).unwrap();
+            assert_eq!(config.consensus_threshold(), threshold);
+        }
+
+        for (numerator, denominator) in [
+            (0, 1),
+            (49, 100),
+            (1, 2),
+            (50, 100),
+            (101, 100),
+            (u64::MAX, 1),
+        ] {
+            assert_eq!(
+                config.set_consensus_threshold(Weight::new(numerator, denominator).unwrap()),
+                Err(RewardCoreError::InvalidConsensusThreshold)
+            );
+        }
+        assert_eq!(
+            config.consensus_threshold(),
+            Weight::new(u64::MAX, u64::MAX).unwrap()
+        );
+
+        let zeroed: Weight = Zeroable::zeroed();
+        assert_eq!(
+            config.set_consensus_threshold(zeroed),
+            Err(RewardCoreError::InvalidConsensusThreshold)
+        );
+    }
+
+    #[test]
+    fn test_set_min_participation() {
+        let mut config = config();
+
+        for (numerator, denominator) in [(0, 1), (1, 3), (1, 1)] {
+            let min_participation = Weight::new(numerator, denominator).unwrap();
+            config.set_min_participation(min_participation).unwrap();
+            assert_eq!(config.min_participation(), min_participation);
+        }
+
+        assert_eq!(
+            config.set_min_participation(Weight::new(3, 2).unwrap()),
+            Err(RewardCoreError::InvalidMinParticipation)
+        );
+
+        let zeroed: Weight = Zeroable::zeroed();
+        assert_eq!(
+            config.set_min_participation(zeroed),
+            Err(RewardCoreError::InvalidMinParticipation)
+        );
+    }
+}
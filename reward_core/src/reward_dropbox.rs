@@ -1,19 +1,34 @@
 use bytemuck::{Pod, Zeroable};
 use jito_bytemuck::{AccountDeserialize, Discriminator};
 use shank::ShankAccount;
-use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::discriminators::Discriminators;
 
+/// A stable deposit address per NCN. Rewards sent to the dropbox and its ATAs are
+/// swept into the current epoch's `EpochRewardMerkleRoot` by `dropbox_to_latest`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable, AccountDeserialize, ShankAccount)]
 #[repr(C)]
-pub struct RewardDropbox {}
+pub struct RewardDropbox {
+    pub ncn: Pubkey,
+    pub bump: u8,
+}
 
 impl Discriminator for RewardDropbox {
     const DISCRIMINATOR: u8 = Discriminators::RewardDropbox as u8;
 }
 
 impl RewardDropbox {
+    pub fn size() -> u64 {
+        8_u64
+            .checked_add(std::mem::size_of::<RewardDropbox>() as u64)
+            .unwrap()
+    }
+
+    pub fn new(ncn: Pubkey, bump: u8) -> Self {
+        Self { ncn, bump }
+    }
+
     /// Returns the seeds for the PDA
     pub fn seeds(ncn: &Pubkey) -> Vec<Vec<u8>> {
         vec![b"REWARD_DROPBOX".as_ref().to_vec(), ncn.to_bytes().to_vec()]
@@ -25,4 +40,36 @@ impl RewardDropbox {
         let (pda, bump) = Pubkey::find_program_address(&seeds_iter, program_id);
         (pda, bump, seeds)
     }
+
+    pub fn load(
+        program_id: &Pubkey,
+        ncn: &Pubkey,
+        account: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<(), ProgramError> {
+        if account.owner.ne(program_id) {
+            msg!("Reward dropbox account has an invalid owner");
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if account.data_is_empty() {
+            msg!("Reward dropbox account data is empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if expect_writable && !account.is_writable {
+            msg!("Reward dropbox account is not writable");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account.data.borrow()[0].ne(&Self::DISCRIMINATOR) {
+            msg!("Reward dropbox account discriminator is invalid");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account
+            .key
+            .ne(&Self::find_program_address(program_id, ncn).0)
+        {
+            msg!("Reward dropbox account is not at the correct PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
 }
@@ -1,5 +1,3 @@
-use std::u64;
-
 use bytemuck::{Pod, Zeroable};
 use jito_bytemuck::types::PodU64;
 use shank::ShankType;
@@ -26,6 +24,14 @@ impl TokenTable {
         self.table.iter().filter(|entry| !entry.is_empty()).count()
     }
 
+    /// The (mint, value) pairs of the filled entries
+    pub fn iter(&self) -> impl Iterator<Item = (&Pubkey, u64)> {
+        self.table
+            .iter()
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| (&entry.mint, u64::from(entry.value)))
+    }
+
     pub fn find_value(&self, mint: &Pubkey) -> Option<u64> {
         self.table
             .iter()
@@ -33,7 +39,13 @@ impl TokenTable {
             .map(|entry| entry.value.into())
     }
 
+    /// Sets the mint's entry, inserting the mint if needed. `EMPTY_ENTRY_VALUE` marks a free
+    /// slot, so a value that would land on it is rejected instead of emptying the entry.
     pub fn set_value(&mut self, mint: &Pubkey, value: u64) -> Result<(), RewardCoreError> {
+        if value == TokenEntry::EMPTY_ENTRY_VALUE {
+            return Err(RewardCoreError::ArithmeticOverflow);
+        }
+
         let entry = self
             .table
             .iter_mut()
@@ -51,6 +63,46 @@ impl TokenTable {
 
         Ok(())
     }
+
+    /// Adds `value` to the mint's entry, inserting the mint if needed
+    pub fn add_value(&mut self, mint: &Pubkey, value: u64) -> Result<(), RewardCoreError> {
+        let current = self.find_value(mint).unwrap_or(0);
+        let new_value = current
+            .checked_add(value)
+            .ok_or(RewardCoreError::ArithmeticOverflow)?;
+
+        self.set_value(mint, new_value)
+    }
+
+    /// Subtracts `value` from the mint's entry
+    pub fn sub_value(&mut self, mint: &Pubkey, value: u64) -> Result<(), RewardCoreError> {
+        let current = self
+            .find_value(mint)
+            .ok_or(RewardCoreError::ArithmeticOverflow)?;
+        let new_value = current
+            .checked_sub(value)
+            .ok_or(RewardCoreError::ArithmeticOverflow)?;
+
+        self.set_value(mint, new_value)
+    }
+
+    /// Adds every entry of `other` to this table
+    pub fn add_table(&mut self, other: &TokenTable) -> Result<(), RewardCoreError> {
+        for entry in other.table.iter().filter(|entry| !entry.is_empty()) {
+            self.add_value(&entry.mint, entry.value.into())?;
+        }
+
+        Ok(())
+    }
+
+    /// Subtracts every entry of `other` from this table
+    pub fn sub_table(&mut self, other: &TokenTable) -> Result<(), RewardCoreError> {
+        for entry in other.table.iter().filter(|entry| !entry.is_empty()) {
+            self.sub_value(&entry.mint, entry.value.into())?;
+        }
+
+        Ok(())
+    }
 }
 
 pub type EpochPriceTable = TokenTable;
@@ -89,3 +141,31 @@ impl Default for TokenEntry {
 
 pub type EpochPriceTableEntry = TokenEntry;
 pub type StakeEntry = TokenEntry;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_cannot_reach_empty_entry_value() {
+        let mint = Pubkey::new_unique();
+        let mut table = TokenTable::new();
+
+        table
+            .add_value(&mint, TokenEntry::EMPTY_ENTRY_VALUE - 1)
+            .unwrap();
+        assert_eq!(
+            table.add_value(&mint, 1),
+            Err(RewardCoreError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            table.set_value(&mint, TokenEntry::EMPTY_ENTRY_VALUE),
+            Err(RewardCoreError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            table.find_value(&mint),
+            Some(TokenEntry::EMPTY_ENTRY_VALUE - 1)
+        );
+        assert_eq!(table.entry_count(), 1);
+    }
+}
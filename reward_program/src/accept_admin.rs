@@ -0,0 +1,41 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::reward_config::RewardConfig;
+use jito_reward_sdk::error::RewardError;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Completes an admin transfer, signed by the pending admin set with `SetNewAdmin`
+pub fn process_accept_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [config, ncn, new_admin] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(new_admin, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, config, true)?;
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = RewardConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    if !config.is_pending_admin(new_admin.key) {
+        msg!("Signer is not the pending reward config admin");
+        return Err(RewardError::IncorrectPendingAdmin.into());
+    }
+
+    msg!(
+        "Transferring reward config admin from {} to {}",
+        config.admin,
+        new_admin.key
+    );
+    config.accept_admin();
+
+    Ok(())
+}
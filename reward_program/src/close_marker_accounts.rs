@@ -1,15 +1,82 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_jsm_core::close_program_account;
+use jito_reward_core::{
+    epoch_reward_distribution_marker::EpochRewardDistributionMarker,
+    epoch_reward_merkle_root::EpochRewardMerkleRoot, reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
 };
 
+/// Closes distribution markers of an epoch once `slots_before_closing_marker_accounts`
+/// have passed since its distribution finished. Markers are passed as (marker, payer)
+/// pairs in the remaining accounts, the rent goes back to the payer of each marker.
 pub fn process_close_marker_accounts(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
-    // Add code here
-    todo!();
+    let [reward_config, ncn, epoch_reward_merkle_root, marker_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Account Checks
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn.key, epoch_reward_merkle_root, false)?;
+
+    if marker_accounts.is_empty() || marker_accounts.len() % 2 != 0 {
+        msg!("Marker accounts must be passed as (marker, payer) pairs");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+
+        if !epoch_reward_merkle_root.distribution_finished() {
+            msg!("Distribution is not finished");
+            return Err(RewardError::DistributionNotFinished.into());
+        }
+
+        let closable_slot = epoch_reward_merkle_root
+            .slot_distribution_finished()
+            .checked_add(epoch_reward_merkle_root.slots_before_closing_marker_accounts())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if Clock::get()?.slot < closable_slot {
+            msg!("Marker accounts can be closed at slot {}", closable_slot);
+            return Err(RewardError::MarkerAccountsNotClosable.into());
+        }
+    }
+
+    let mut remaining = marker_accounts;
+    while let [marker, payer, rest @ ..] = remaining {
+        remaining = rest;
+
+        EpochRewardDistributionMarker::load(program_id, marker, true)?;
+
+        {
+            let marker_data = marker.data.borrow();
+            let marker = EpochRewardDistributionMarker::try_from_slice_unchecked(&marker_data)?;
+
+            if marker
+                .epoch_reward_merkle_root
+                .ne(epoch_reward_merkle_root.key)
+            {
+                msg!("Marker does not belong to the epoch reward merkle root");
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if marker.payer.ne(payer.key) {
+                msg!("Marker rent must be returned to its payer");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        msg!("Closing marker {}", marker.key);
+        close_program_account(program_id, marker, payer)?;
+    }
+
+    Ok(())
 }
@@ -0,0 +1,95 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_reward_core::reward_config::RewardConfig;
+use jito_reward_sdk::error::RewardError;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg};
+
+/// Fails if the pause authority has paused the program.
+/// The reward config is expected to have been loaded by the caller.
+///
+/// The pause stops rewards from moving: deposits, dropbox sweeps, distribution, rollovers and
+/// early finalization. Voting, ticket updates, closing markers, admin instructions and slashing
+/// are never paused, and an epoch whose voting window is over can always be finalized, so a
+/// pause cannot keep operators from being slashed.
+pub fn check_not_paused(reward_config: &AccountInfo) -> ProgramResult {
+    let reward_config_data = reward_config.data.borrow();
+    let reward_config = RewardConfig::try_from_slice_unchecked(&reward_config_data)?;
+
+    if reward_config.paused() {
+        msg!("Reward program is paused");
+        return Err(RewardError::ProgramPaused.into());
+    }
+
+    Ok(())
+}
+
+/// Finalizing before the voting window ends is paused, finalizing after it is not so the
+/// pause cannot hold an epoch in voting and keep its operators from being slashed
+pub fn check_finalize_not_paused(reward_config: &AccountInfo, voting_done: bool) -> ProgramResult {
+    if voting_done {
+        return Ok(());
+    }
+
+    check_not_paused(reward_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use jito_bytemuck::Discriminator;
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+
+    fn reward_config_data(paused: bool) -> Vec<u8> {
+        let mut data = vec![0; RewardConfig::size() as usize];
+        data[0] = RewardConfig::DISCRIMINATOR;
+        let reward_config = RewardConfig::try_from_slice_unchecked_mut(&mut data).unwrap();
+        *reward_config = RewardConfig::new(&Pubkey::new_unique(), &Pubkey::new_unique(), 1, 1, 1);
+        reward_config.set_paused(paused);
+        data
+    }
+
+    #[test]
+    fn test_pause_scope() {
+        let key = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+
+        let mut lamports = 0;
+        let mut data = reward_config_data(false);
+        let reward_config = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert_eq!(check_not_paused(&reward_config), Ok(()));
+        assert_eq!(check_finalize_not_paused(&reward_config, false), Ok(()));
+        assert_eq!(check_finalize_not_paused(&reward_config, true), Ok(()));
+
+        let mut lamports = 0;
+        let mut data = reward_config_data(true);
+        let reward_config = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert_eq!(
+            check_not_paused(&reward_config),
+            Err(RewardError::ProgramPaused.into())
+        );
+        // Early finalization is paused, finalizing after the voting window never is
+        assert_eq!(
+            check_finalize_not_paused(&reward_config, false),
+            Err(RewardError::ProgramPaused.into())
+        );
+        assert_eq!(check_finalize_not_paused(&reward_config, true), Ok(()));
+    }
+}
@@ -1,16 +1,260 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    reward_config::RewardConfig,
+    token_table::TokenTable,
+};
+use jito_reward_sdk::error::RewardError;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+};
+
+use crate::{
+    config_helpers::check_not_paused,
+    deposit_rewards::check_deposit_epoch,
+    token_helpers::{
+        get_token_balance, load_associated_token_account, load_mint_decimals, load_token_program,
+    },
 };
 
+/// Moves the rewards of an epoch that finished voting without consensus into the current
+/// epoch. Token balances are passed as (mint, delinquent_token_account, current_token_account,
+/// token_program) groups in the remaining accounts and every deposited mint must be included.
 pub fn process_delinquent_to_latest(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
-    // Add code here
+    let [restaking_config, reward_config, ncn, delinquent_epoch_reward_merkle_root, current_epoch_reward_merkle_root, token_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Account Checks
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    check_not_paused(reward_config)?;
+    EpochRewardMerkleRoot::load(
+        program_id,
+        ncn.key,
+        delinquent_epoch_reward_merkle_root,
+        true,
+    )?;
+    check_deposit_epoch(
+        program_id,
+        restaking_config,
+        ncn,
+        current_epoch_reward_merkle_root,
+    )?;
+
+    if delinquent_epoch_reward_merkle_root
+        .key
+        .eq(current_epoch_reward_merkle_root.key)
+    {
+        msg!("Rewards cannot be rolled over into the same epoch");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if token_accounts.len() % 4 != 0 {
+        msg!("Token accounts must be passed in groups of (mint, delinquent token account, current token account, token program)");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let ncn_epoch = {
+        let delinquent_epoch_reward_merkle_root_data =
+            delinquent_epoch_reward_merkle_root.data.borrow();
+        let delinquent_epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked(
+            &delinquent_epoch_reward_merkle_root_data,
+        )?;
+        let ncn_epoch = u64::from(delinquent_epoch_reward_merkle_root.ncn_epoch);
+
+        // Only an epoch that FinalizeConsensus recorded without a consensus root can roll over
+        match delinquent_epoch_reward_merkle_root.state()? {
+            EpochRewardState::Finalized => {}
+            EpochRewardState::AcceptingDeposits | EpochRewardState::Voting => {
+                msg!("Voting for epoch {} is not finalized", ncn_epoch);
+                return Err(RewardError::EpochNotFinalized.into());
+            }
+            EpochRewardState::RolledOver => {
+                msg!("Rewards for epoch {} were already rolled over", ncn_epoch);
+                return Err(RewardError::RolledOver.into());
+            }
+            EpochRewardState::Distributing | EpochRewardState::Distributed => {
+                msg!(
+                    "Epoch {} reached consensus, rewards must be distributed",
+                    ncn_epoch
+                );
+                return Err(RewardError::ConsensusReached.into());
+            }
+        }
+
+        if delinquent_epoch_reward_merkle_root.consensus().is_some() {
+            msg!(
+                "Epoch {} reached consensus, rewards must be distributed",
+                ncn_epoch
+            );
+            return Err(RewardError::ConsensusReached.into());
+        }
+
+        check_rollover_mints(
+            &delinquent_epoch_reward_merkle_root.deposited_rewards,
+            token_accounts,
+        )?;
+
+        ncn_epoch
+    };
+
+    move_rewards_to_latest(
+        program_id,
+        ncn.key,
+        ncn_epoch,
+        delinquent_epoch_reward_merkle_root,
+        current_epoch_reward_merkle_root,
+        token_accounts,
+    )?;
+
+    let mut delinquent_epoch_reward_merkle_root_data =
+        delinquent_epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let delinquent_epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked_mut(
+        &mut delinquent_epoch_reward_merkle_root_data,
+    )?;
+    delinquent_epoch_reward_merkle_root.transition(EpochRewardState::RolledOver)?;
+
+    Ok(())
+}
+
+/// Fails unless every mint deposited to the epoch has a token account group, a partial move
+/// would strand the remaining mints since the epoch's state changes afterwards. Moving all of
+/// them cannot get stuck on one mint, deposits only accept mints without a freeze authority,
+/// transfer hook or non-transferable extension, none of which can be added to a mint later.
+pub fn check_rollover_mints(
+    deposited_rewards: &TokenTable,
+    token_accounts: &[AccountInfo],
+) -> ProgramResult {
+    for entry in deposited_rewards
+        .table
+        .iter()
+        .filter(|entry| !entry.is_empty() && entry.mint.ne(&spl_token::native_mint::id()))
+    {
+        let included = token_accounts
+            .iter()
+            .step_by(4)
+            .any(|mint| mint.key.eq(&entry.mint));
+
+        if !included {
+            msg!("Deposited mint {} is missing from the rollover", entry.mint);
+            return Err(RewardError::MissingRolloverMint.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves every token balance and the native SOL above rent exemption held by an epoch into the
+/// current epoch, recording them as deposits there. Token balances are passed as (mint,
+/// token_account, current_token_account, token_program) groups.
+pub fn move_rewards_to_latest<'a, 'info>(
+    program_id: &Pubkey,
+    ncn: &Pubkey,
+    ncn_epoch: u64,
+    epoch_reward_merkle_root: &'a AccountInfo<'info>,
+    current_epoch_reward_merkle_root: &'a AccountInfo<'info>,
+    token_accounts: &'a [AccountInfo<'info>],
+) -> ProgramResult {
+    let (_, bump, mut seeds) =
+        EpochRewardMerkleRoot::find_program_address(program_id, ncn, ncn_epoch);
+    seeds.push(vec![bump]);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+
+    let mut remaining = token_accounts;
+    while let [mint, token_account, current_token_account, token_program, rest @ ..] = remaining {
+        remaining = rest;
+
+        load_token_program(token_program)?;
+        let decimals = load_mint_decimals(token_program.key, mint)?;
+        load_associated_token_account(
+            token_program.key,
+            epoch_reward_merkle_root.key,
+            mint.key,
+            token_account,
+            true,
+        )?;
+        load_associated_token_account(
+            token_program.key,
+            current_epoch_reward_merkle_root.key,
+            mint.key,
+            current_token_account,
+            true,
+        )?;
+
+        let amount = get_token_balance(token_account)?;
+        if amount == 0 {
+            continue;
+        }
+
+        let balance_before = get_token_balance(current_token_account)?;
+
+        invoke_signed(
+            &spl_token_2022::instruction::transfer_checked(
+                token_program.key,
+                token_account.key,
+                mint.key,
+                current_token_account.key,
+                epoch_reward_merkle_root.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[
+                token_account.clone(),
+                mint.clone(),
+                current_token_account.clone(),
+                epoch_reward_merkle_root.clone(),
+            ],
+            &[seeds.as_slice()],
+        )?;
+
+        let received = get_token_balance(current_token_account)?
+            .checked_sub(balance_before)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        msg!("Rolled over {} of mint {}", received, mint.key);
+
+        let mut current_epoch_reward_merkle_root_data =
+            current_epoch_reward_merkle_root.try_borrow_mut_data()?;
+        let current_epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked_mut(
+            &mut current_epoch_reward_merkle_root_data,
+        )?;
+        current_epoch_reward_merkle_root
+            .deposited_rewards
+            .add_value(mint.key, received)?;
+    }
+
+    // Native SOL rewards are the lamports above the epoch account's rent exemption
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(epoch_reward_merkle_root.data_len());
+    let lamports = epoch_reward_merkle_root
+        .lamports()
+        .saturating_sub(rent_exempt_lamports);
+
+    if lamports > 0 {
+        msg!("Rolled over {} lamports", lamports);
+
+        **epoch_reward_merkle_root.try_borrow_mut_lamports()? = rent_exempt_lamports;
+        **current_epoch_reward_merkle_root.try_borrow_mut_lamports()? =
+            current_epoch_reward_merkle_root
+                .lamports()
+                .checked_add(lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut current_epoch_reward_merkle_root_data =
+            current_epoch_reward_merkle_root.try_borrow_mut_data()?;
+        let current_epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked_mut(
+            &mut current_epoch_reward_merkle_root_data,
+        )?;
+        current_epoch_reward_merkle_root
+            .deposited_rewards
+            .add_value(&spl_token::native_mint::id(), lamports)?;
+    }
 
-    todo!();
+    Ok(())
 }
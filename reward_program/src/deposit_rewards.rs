@@ -1,13 +1,246 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_jsm_core::loader::{load_signer, load_system_program};
+use jito_restaking_core::{config::Config as RestakingConfig, ncn::Ncn};
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
+use jito_weight_table_core::weight_table::WeightTable;
+use jito_weight_table_program::ID as WEIGHT_TABLE_PROGRAM_ID;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, system_instruction, sysvar::Sysvar,
+};
+
+use crate::{
+    config_helpers::check_not_paused,
+    restaking_helpers::get_ncn_epoch,
+    token_helpers::{
+        get_token_balance, load_associated_token_account, load_reward_mint, load_token_program,
+    },
 };
 
-pub fn process_deposit_rewards(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    // Add code here
+/// Deposits SPL Token or Token-2022 rewards into the merkle root's associated token account.
+/// The amount recorded is what the merkle root received, so transfer fees are excluded.
+/// Only mints listed in the NCN's weight table can be deposited.
+pub fn process_deposit_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let [restaking_config, reward_config, ncn, epoch_reward_merkle_root, mint, depositor_token_account, epoch_reward_merkle_root_token_account, depositor, token_program, weight_table] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Account Checks
+    load_signer(depositor, false)?;
+    load_token_program(token_program)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    check_not_paused(reward_config)?;
+    let ncn_epoch =
+        check_deposit_epoch(program_id, restaking_config, ncn, epoch_reward_merkle_root)?;
+
+    // Native SOL is tracked under the native mint, so wrapped SOL would be counted twice
+    if mint.key.eq(&spl_token::native_mint::id()) {
+        msg!("Native SOL rewards must be deposited with DepositSolRewards");
+        return Err(RewardError::NativeMintDeposit.into());
+    }
+    if amount == 0 {
+        msg!("Deposit amount must be greater than zero");
+        return Err(RewardError::InvalidDepositAmount.into());
+    }
+
+    check_supported_reward_mint(ncn, weight_table, ncn_epoch, mint.key)?;
+    let decimals = load_reward_mint(token_program.key, mint)?;
+    load_associated_token_account(
+        token_program.key,
+        depositor.key,
+        mint.key,
+        depositor_token_account,
+        true,
+    )?;
+    load_associated_token_account(
+        token_program.key,
+        epoch_reward_merkle_root.key,
+        mint.key,
+        epoch_reward_merkle_root_token_account,
+        true,
+    )?;
+
+    let balance_before = get_token_balance(epoch_reward_merkle_root_token_account)?;
+
+    invoke(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            depositor_token_account.key,
+            mint.key,
+            epoch_reward_merkle_root_token_account.key,
+            depositor.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            depositor_token_account.clone(),
+            mint.clone(),
+            epoch_reward_merkle_root_token_account.clone(),
+            depositor.clone(),
+        ],
+    )?;
+
+    let received = get_token_balance(epoch_reward_merkle_root_token_account)?
+        .checked_sub(balance_before)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("Deposited {} of mint {}", received, mint.key);
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+    epoch_reward_merkle_root
+        .deposited_rewards
+        .add_value(mint.key, received)?;
+
+    Ok(())
+}
+
+/// Deposits native SOL rewards as lamports held by the merkle root account
+pub fn process_deposit_sol_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let [restaking_config, reward_config, ncn, epoch_reward_merkle_root, depositor, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Account Checks
+    load_signer(depositor, true)?;
+    load_system_program(system_program)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    check_not_paused(reward_config)?;
+    check_deposit_epoch(program_id, restaking_config, ncn, epoch_reward_merkle_root)?;
+
+    if amount == 0 {
+        msg!("Deposit amount must be greater than zero");
+        return Err(RewardError::InvalidDepositAmount.into());
+    }
+
+    invoke(
+        &system_instruction::transfer(depositor.key, epoch_reward_merkle_root.key, amount),
+        &[
+            depositor.clone(),
+            epoch_reward_merkle_root.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    msg!("Deposited {} lamports", amount);
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+    epoch_reward_merkle_root
+        .deposited_rewards
+        .add_value(&spl_token::native_mint::id(), amount)?;
+
+    Ok(())
+}
+
+/// Rewards can only be deposited into the merkle root of the current epoch, while it is
+/// accepting deposits. Returns the epoch of the merkle root.
+pub fn check_deposit_epoch(
+    program_id: &Pubkey,
+    restaking_config: &AccountInfo,
+    ncn: &AccountInfo,
+    epoch_reward_merkle_root: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    RestakingConfig::load(&restaking_program, restaking_config, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn.key, epoch_reward_merkle_root, true)?;
+
+    let current_epoch = {
+        let restaking_config_data = restaking_config.data.borrow();
+        let restaking_config = RestakingConfig::try_from_slice_unchecked(&restaking_config_data)?;
+        get_ncn_epoch(Clock::get()?.slot, restaking_config.epoch_length())?
+    };
+
+    let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+    let ncn_epoch = u64::from(epoch_reward_merkle_root.ncn_epoch);
+
+    if ncn_epoch != current_epoch {
+        msg!(
+            "Rewards for epoch {} cannot be deposited in epoch {}",
+            ncn_epoch,
+            current_epoch
+        );
+        return Err(RewardError::EpochIsNotCurrent.into());
+    }
+
+    if epoch_reward_merkle_root.state()? != EpochRewardState::AcceptingDeposits {
+        msg!("Epoch {} is no longer accepting deposits", ncn_epoch);
+        return Err(RewardError::EpochNotAcceptingDeposits.into());
+    }
+
+    Ok(ncn_epoch)
+}
+
+/// Only mints listed with a weight in the NCN's weight table can be deposited, so the
+/// deposited rewards table cannot be filled with arbitrary mints. The weight table of the
+/// epoch is usually created once the epoch is over, so the previous epoch's table is accepted.
+pub fn check_supported_reward_mint(
+    ncn: &AccountInfo,
+    weight_table: &AccountInfo,
+    ncn_epoch: u64,
+    mint: &Pubkey,
+) -> ProgramResult {
+    let weight_table_epoch = [Some(ncn_epoch), ncn_epoch.checked_sub(1)]
+        .into_iter()
+        .flatten()
+        .find(|epoch| {
+            weight_table.key.eq(&WeightTable::find_program_address(
+                &WEIGHT_TABLE_PROGRAM_ID,
+                ncn.key,
+                *epoch,
+            )
+            .0)
+        })
+        .ok_or_else(|| {
+            msg!(
+                "Weight table is not the NCN's weight table for epoch {} or the one before",
+                ncn_epoch
+            );
+            ProgramError::InvalidAccountData
+        })?;
+
+    WeightTable::load(
+        &WEIGHT_TABLE_PROGRAM_ID,
+        weight_table,
+        ncn,
+        weight_table_epoch,
+        false,
+    )?;
+
+    let weight_table_data = weight_table.data.borrow();
+    let weight_table = WeightTable::try_from_slice_unchecked(&weight_table_data)?;
+    if !weight_table.has_nonzero_weight(mint) {
+        msg!(
+            "Mint {} is not in the weight table of epoch {}",
+            mint,
+            weight_table_epoch
+        );
+        return Err(RewardError::UnsupportedRewardMint.into());
+    }
 
-    todo!();
+    Ok(())
 }
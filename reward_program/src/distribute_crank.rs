@@ -1,13 +1,270 @@
-use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{
+    create_account,
+    loader::{load_signer, load_system_account, load_system_program},
+};
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{
+    epoch_reward_distribution_marker::EpochRewardDistributionMarker,
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    merkle_tree::{hash_leaf, verify_proof},
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program::invoke_signed, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    config_helpers::check_not_paused,
+    token_helpers::{load_associated_token_account, load_mint_decimals, load_token_program},
 };
 
-pub fn process_distribute_crank(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    // Add code here
+/// Pays out one SPL Token or Token-2022 leaf of the epoch's consensus merkle root.
+/// Permissionless, the distribution marker prevents the same leaf from being paid twice.
+pub fn process_distribute_crank(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let [reward_config, ncn, epoch_reward_merkle_root, epoch_reward_distribution_marker, mint, recipient, epoch_reward_merkle_root_token_account, recipient_token_account, payer, token_program, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    load_token_program(token_program)?;
+    let decimals = load_mint_decimals(token_program.key, mint)?;
+    load_associated_token_account(
+        token_program.key,
+        epoch_reward_merkle_root.key,
+        mint.key,
+        epoch_reward_merkle_root_token_account,
+        true,
+    )?;
+    load_associated_token_account(
+        token_program.key,
+        recipient.key,
+        mint.key,
+        recipient_token_account,
+        true,
+    )?;
+
+    let ncn_epoch = distribute(
+        program_id,
+        reward_config,
+        ncn,
+        epoch_reward_merkle_root,
+        epoch_reward_distribution_marker,
+        mint.key,
+        recipient,
+        payer,
+        system_program,
+        amount,
+        &proof,
+    )?;
+
+    let (_, epoch_reward_merkle_root_bump, mut epoch_reward_merkle_root_seeds) =
+        EpochRewardMerkleRoot::find_program_address(program_id, ncn.key, ncn_epoch);
+    epoch_reward_merkle_root_seeds.push(vec![epoch_reward_merkle_root_bump]);
+    let epoch_reward_merkle_root_seeds: Vec<&[u8]> = epoch_reward_merkle_root_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect();
+
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            epoch_reward_merkle_root_token_account.key,
+            mint.key,
+            recipient_token_account.key,
+            epoch_reward_merkle_root.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            epoch_reward_merkle_root_token_account.clone(),
+            mint.clone(),
+            recipient_token_account.clone(),
+            epoch_reward_merkle_root.clone(),
+        ],
+        &[epoch_reward_merkle_root_seeds.as_slice()],
+    )?;
+
+    Ok(())
+}
+
+/// Pays out one native SOL leaf of the epoch's consensus merkle root
+pub fn process_distribute_sol_crank(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let [reward_config, ncn, epoch_reward_merkle_root, epoch_reward_distribution_marker, recipient, payer, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !recipient.is_writable {
+        msg!("Recipient account is not writable");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    distribute(
+        program_id,
+        reward_config,
+        ncn,
+        epoch_reward_merkle_root,
+        epoch_reward_distribution_marker,
+        &spl_token::native_mint::id(),
+        recipient,
+        payer,
+        system_program,
+        amount,
+        &proof,
+    )?;
+
+    // The deposited lamports sit on top of the merkle root's rent exemption
+    **epoch_reward_merkle_root.try_borrow_mut_lamports()? = epoch_reward_merkle_root
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Verifies the leaf against the consensus root, creates the distribution marker and
+/// records the payout. Returns the merkle root's epoch.
+#[allow(clippy::too_many_arguments)]
+fn distribute<'a, 'info>(
+    program_id: &Pubkey,
+    reward_config: &AccountInfo,
+    ncn: &AccountInfo,
+    epoch_reward_merkle_root: &AccountInfo,
+    epoch_reward_distribution_marker: &'a AccountInfo<'info>,
+    mint: &Pubkey,
+    recipient: &AccountInfo,
+    payer: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    amount: u64,
+    proof: &[[u8; 32]],
+) -> Result<u64, ProgramError> {
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_system_account(epoch_reward_distribution_marker, true)?;
+    load_signer(payer, true)?;
+    load_system_program(system_program)?;
+
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn.key, epoch_reward_merkle_root, true)?;
+
+    check_not_paused(reward_config)?;
+
+    // Distribution pays out against the root recorded by FinalizeConsensus
+    let (ncn_epoch, consensus) = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+        let ncn_epoch = u64::from(epoch_reward_merkle_root.ncn_epoch);
+
+        match epoch_reward_merkle_root.state()? {
+            EpochRewardState::Finalized | EpochRewardState::Distributing => {}
+            EpochRewardState::AcceptingDeposits | EpochRewardState::Voting => {
+                msg!("Voting for epoch {} is not finalized", ncn_epoch);
+                return Err(RewardError::EpochNotFinalized.into());
+            }
+            EpochRewardState::RolledOver => {
+                msg!("Rewards for epoch {} were rolled over", ncn_epoch);
+                return Err(RewardError::RolledOver.into());
+            }
+            EpochRewardState::Distributed => {
+                msg!("Rewards for epoch {} cannot be distributed", ncn_epoch);
+                return Err(RewardError::EpochNotDistributing.into());
+            }
+        }
+
+        let consensus = epoch_reward_merkle_root.consensus().ok_or_else(|| {
+            msg!("Epoch {} did not reach consensus", ncn_epoch);
+            RewardError::ConsensusNotReached
+        })?;
+
+        (ncn_epoch, consensus)
+    };
+
+    if !verify_proof(
+        proof,
+        &consensus.root,
+        hash_leaf(recipient.key, mint, amount),
+    ) {
+        msg!("Merkle proof does not match the consensus root");
+        return Err(RewardError::InvalidMerkleProof.into());
+    }
+
+    let (
+        epoch_reward_distribution_marker_pubkey,
+        epoch_reward_distribution_marker_bump,
+        mut epoch_reward_distribution_marker_seeds,
+    ) = EpochRewardDistributionMarker::find_program_address(
+        program_id,
+        epoch_reward_merkle_root.key,
+        mint,
+        recipient.key,
+        ncn_epoch,
+    );
+    epoch_reward_distribution_marker_seeds.push(vec![epoch_reward_distribution_marker_bump]);
+
+    if epoch_reward_distribution_marker_pubkey.ne(epoch_reward_distribution_marker.key) {
+        msg!("Epoch reward distribution marker account is not at the correct PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let current_slot = Clock::get()?.slot;
+
+    msg!(
+        "Distributing {} of mint {} to {} (epoch {})",
+        amount,
+        mint,
+        recipient.key,
+        ncn_epoch
+    );
+
+    create_account(
+        payer,
+        epoch_reward_distribution_marker,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        EpochRewardDistributionMarker::size(),
+        &epoch_reward_distribution_marker_seeds,
+    )?;
+
+    let mut epoch_reward_distribution_marker_data =
+        epoch_reward_distribution_marker.try_borrow_mut_data()?;
+    epoch_reward_distribution_marker_data[0] = EpochRewardDistributionMarker::DISCRIMINATOR;
+    let epoch_reward_distribution_marker =
+        EpochRewardDistributionMarker::try_from_slice_unchecked_mut(
+            &mut epoch_reward_distribution_marker_data,
+        )?;
+    *epoch_reward_distribution_marker =
+        EpochRewardDistributionMarker::new(*epoch_reward_merkle_root.key, *payer.key, current_slot);
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+    epoch_reward_merkle_root.record_distribution(mint, amount, current_slot)?;
 
-    todo!();
+    Ok(ncn_epoch)
 }
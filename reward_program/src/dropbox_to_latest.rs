@@ -1,13 +1,171 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_reward_core::{
+    epoch_reward_merkle_root::EpochRewardMerkleRoot, reward_config::RewardConfig,
+    reward_dropbox::RewardDropbox,
+};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+};
+
+use crate::{
+    config_helpers::check_not_paused,
+    deposit_rewards::{check_deposit_epoch, check_supported_reward_mint},
+    token_helpers::{
+        get_token_balance, load_associated_token_account, load_reward_mint, load_token_program,
+    },
 };
 
+/// Sweeps everything held by the reward dropbox into the current epoch's merkle root.
+/// Token balances are passed as (mint, dropbox_token_account, epoch_reward_merkle_root_token_account,
+/// token_program) groups in the remaining accounts, lamports above rent exemption are always swept.
+/// Like deposits, only mints in the NCN's weight table can be swept. Wrapped SOL is unwrapped by
+/// closing the dropbox's native mint token account, so it is swept with the lamports.
 pub fn process_dropbox_to_latest(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    // Add code here
+    let [restaking_config, reward_config, ncn, reward_dropbox, epoch_reward_merkle_root, weight_table, token_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Account Checks
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    check_not_paused(reward_config)?;
+    RewardDropbox::load(program_id, ncn.key, reward_dropbox, true)?;
+    // Only the latest epoch can receive rewards
+    let ncn_epoch =
+        check_deposit_epoch(program_id, restaking_config, ncn, epoch_reward_merkle_root)?;
+
+    if token_accounts.len() % 4 != 0 {
+        msg!("Token accounts must be passed in groups of (mint, dropbox token account, merkle root token account, token program)");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let reward_dropbox_bump = {
+        let reward_dropbox_data = reward_dropbox.data.borrow();
+        RewardDropbox::try_from_slice_unchecked(&reward_dropbox_data)?.bump
+    };
+    let mut reward_dropbox_seeds = RewardDropbox::seeds(ncn.key);
+    reward_dropbox_seeds.push(vec![reward_dropbox_bump]);
+    let reward_dropbox_seeds: Vec<&[u8]> = reward_dropbox_seeds
+        .iter()
+        .map(|seed| seed.as_slice())
+        .collect();
+
+    let mut remaining = token_accounts;
+    while let [mint, reward_dropbox_token_account, epoch_reward_merkle_root_token_account, token_program, rest @ ..] =
+        remaining
+    {
+        remaining = rest;
+
+        load_token_program(token_program)?;
+
+        // Native SOL is tracked as lamports, closing the account moves them into the dropbox
+        if mint.key.eq(&spl_token::native_mint::id()) {
+            load_associated_token_account(
+                token_program.key,
+                reward_dropbox.key,
+                mint.key,
+                reward_dropbox_token_account,
+                true,
+            )?;
+
+            msg!("Unwrapping wrapped SOL held by the reward dropbox");
+            invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    token_program.key,
+                    reward_dropbox_token_account.key,
+                    reward_dropbox.key,
+                    reward_dropbox.key,
+                    &[],
+                )?,
+                &[reward_dropbox_token_account.clone(), reward_dropbox.clone()],
+                &[reward_dropbox_seeds.as_slice()],
+            )?;
+            continue;
+        }
+        check_supported_reward_mint(ncn, weight_table, ncn_epoch, mint.key)?;
+        let decimals = load_reward_mint(token_program.key, mint)?;
+        load_associated_token_account(
+            token_program.key,
+            reward_dropbox.key,
+            mint.key,
+            reward_dropbox_token_account,
+            true,
+        )?;
+        load_associated_token_account(
+            token_program.key,
+            epoch_reward_merkle_root.key,
+            mint.key,
+            epoch_reward_merkle_root_token_account,
+            true,
+        )?;
+
+        let amount = get_token_balance(reward_dropbox_token_account)?;
+        if amount == 0 {
+            continue;
+        }
+
+        let balance_before = get_token_balance(epoch_reward_merkle_root_token_account)?;
+
+        invoke_signed(
+            &spl_token_2022::instruction::transfer_checked(
+                token_program.key,
+                reward_dropbox_token_account.key,
+                mint.key,
+                epoch_reward_merkle_root_token_account.key,
+                reward_dropbox.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[
+                reward_dropbox_token_account.clone(),
+                mint.clone(),
+                epoch_reward_merkle_root_token_account.clone(),
+                reward_dropbox.clone(),
+            ],
+            &[reward_dropbox_seeds.as_slice()],
+        )?;
+
+        let received = get_token_balance(epoch_reward_merkle_root_token_account)?
+            .checked_sub(balance_before)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        msg!("Swept {} of mint {}", received, mint.key);
+
+        let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+        let epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked_mut(
+            &mut epoch_reward_merkle_root_data,
+        )?;
+        epoch_reward_merkle_root
+            .deposited_rewards
+            .add_value(mint.key, received)?;
+    }
+
+    // The dropbox only needs to stay rent exempt, anything above that is rewards
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(reward_dropbox.data_len());
+    let lamports = reward_dropbox
+        .lamports()
+        .saturating_sub(rent_exempt_lamports);
+
+    if lamports > 0 {
+        msg!("Swept {} lamports", lamports);
+
+        **reward_dropbox.try_borrow_mut_lamports()? = rent_exempt_lamports;
+        **epoch_reward_merkle_root.try_borrow_mut_lamports()? = epoch_reward_merkle_root
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+        let epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked_mut(
+            &mut epoch_reward_merkle_root_data,
+        )?;
+        epoch_reward_merkle_root
+            .deposited_rewards
+            .add_value(&spl_token::native_mint::id(), lamports)?;
+    }
 
-    todo!();
+    Ok(())
 }
@@ -0,0 +1,130 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
+use jito_weight_table_core::weight_table::WeightTable;
+use jito_weight_table_program::ID as WEIGHT_TABLE_PROGRAM_ID;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+use crate::config_helpers::check_finalize_not_paused;
+
+/// Tallies the votes of an epoch against its finalized weight table and records the outcome.
+/// Can be called as soon as a root has the threshold of the registered stake behind it,
+/// otherwise once the voting window is over, in which case the epoch is finalized without a
+/// consensus root and can be rolled over. Without a finalized weight table the votes cannot be
+/// tallied, so the epoch finalizes without consensus once the voting window is over.
+/// While paused only the early finalization is blocked.
+pub fn process_finalize_consensus(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [reward_config, ncn, weight_table, epoch_reward_merkle_root] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn.key, epoch_reward_merkle_root, true)?;
+
+    let ncn_epoch = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+        u64::from(epoch_reward_merkle_root.ncn_epoch)
+    };
+
+    // The weight table may never have been created or finalized, the epoch can then only be
+    // finalized without consensus once voting is over
+    let expected_weight_table =
+        WeightTable::find_program_address(&WEIGHT_TABLE_PROGRAM_ID, ncn.key, ncn_epoch).0;
+    if weight_table.key.ne(&expected_weight_table) {
+        msg!("Weight table incorrect PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let weight_table_exists = !weight_table.data_is_empty();
+    if weight_table_exists {
+        WeightTable::load(
+            &WEIGHT_TABLE_PROGRAM_ID,
+            weight_table,
+            ncn,
+            ncn_epoch,
+            false,
+        )?;
+    }
+    let weight_table_data = weight_table.data.borrow();
+    let weight_table = if weight_table_exists {
+        Some(WeightTable::try_from_slice_unchecked(&weight_table_data)?)
+            .filter(|weight_table| weight_table.finalized())
+    } else {
+        None
+    };
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+
+    let current_slot = Clock::get()?.slot;
+    let valid_voting_slots = epoch_reward_merkle_root.valid_voting_slots();
+    let consensus_threshold = epoch_reward_merkle_root.consensus_threshold();
+    let min_participation = epoch_reward_merkle_root.min_participation();
+
+    epoch_reward_merkle_root.open_voting(current_slot)?;
+    match epoch_reward_merkle_root.state()? {
+        EpochRewardState::Voting => {}
+        EpochRewardState::AcceptingDeposits => {
+            msg!("Voting for epoch {} starts after the epoch ends", ncn_epoch);
+            return Err(RewardError::VotingNotStarted.into());
+        }
+        _ => {
+            msg!("Voting for epoch {} is already finalized", ncn_epoch);
+            return Err(RewardError::EpochNotVoting.into());
+        }
+    }
+
+    let voting_done = epoch_reward_merkle_root.is_voting_done(current_slot, valid_voting_slots);
+    check_finalize_not_paused(reward_config, voting_done)?;
+
+    let tally = match weight_table {
+        Some(weight_table) => epoch_reward_merkle_root.get_highest_voted_root(weight_table)?,
+        None if voting_done => None,
+        None => {
+            msg!("Weight table for epoch {} is not finalized", ncn_epoch);
+            return Err(RewardError::WeightTableNotFinalized.into());
+        }
+    };
+
+    if !epoch_reward_merkle_root.can_finalize(
+        tally.as_ref(),
+        current_slot,
+        valid_voting_slots,
+        &consensus_threshold,
+        &min_participation,
+    ) {
+        msg!(
+            "Voting for epoch {} has not reached consensus of the registered stake yet",
+            ncn_epoch
+        );
+        return Err(RewardError::VotingNotDone.into());
+    }
+
+    epoch_reward_merkle_root.finalize_consensus(tally, &consensus_threshold, &min_participation)?;
+
+    match epoch_reward_merkle_root.consensus() {
+        Some(consensus) => msg!(
+            "Epoch {} finalized with {} of {} registered stake",
+            ncn_epoch,
+            consensus.root_stake,
+            consensus.registered_stake
+        ),
+        None => msg!("Epoch {} finalized without consensus", ncn_epoch),
+    }
+
+    Ok(())
+}
@@ -16,6 +16,7 @@ pub fn process_initialize_config(
     accounts: &[AccountInfo],
     valid_voting_slots: u64,
     slots_before_closing_marker_accounts: u64,
+    slash_amount: u64,
 ) -> ProgramResult {
     let [config, ncn, admin, system_program] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -59,7 +60,11 @@ pub fn process_initialize_config(
         admin.key,
         valid_voting_slots,
         slots_before_closing_marker_accounts,
+        slash_amount,
     );
+    config.set_valid_voting_slots(valid_voting_slots)?;
+    config.set_slots_before_closing_marker_accounts(slots_before_closing_marker_accounts)?;
+    config.set_slash_amount(slash_amount)?;
 
     Ok(())
 }
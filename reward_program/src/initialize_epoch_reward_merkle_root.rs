@@ -5,8 +5,10 @@ use jito_jsm_core::{
 };
 use jito_restaking_core::{config::Config as RestakingConfig, ncn::Ncn};
 use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
-use jito_reward_core::epoch_reward_merkle_root::EpochRewardMerkleRoot;
-use jito_vault_core::config::Config as RewardConfig;
+use jito_reward_core::{
+    epoch_reward_merkle_root::EpochRewardMerkleRoot, error::RewardCoreError,
+    reward_config::RewardConfig,
+};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
     program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
@@ -31,15 +33,19 @@ pub fn process_initialize_epoch_reward_merkle_root(
 
     Ncn::load(&restaking_program, ncn, false)?;
     RestakingConfig::load(&restaking_program, restaking_config, false)?;
-    RewardConfig::load(program_id, reward_config, false)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
 
-    let ncn_epoch = {
+    let epoch_length = {
         let restaking_config_account_data = restaking_config.data.borrow();
         let restaking_config_account =
             RestakingConfig::try_from_slice_unchecked(&restaking_config_account_data)?;
-        EpochRewardMerkleRoot::epoch(Clock::get()?.slot, restaking_config_account.epoch_length())
-            .unwrap()
+        restaking_config_account.epoch_length()
     };
+    let ncn_epoch = EpochRewardMerkleRoot::epoch(Clock::get()?.slot, epoch_length)
+        .ok_or(RewardCoreError::ArithmeticOverflow)?;
+    let epoch_start_slot = ncn_epoch
+        .checked_mul(epoch_length)
+        .ok_or(RewardCoreError::ArithmeticOverflow)?;
 
     let (
         epoch_reward_merkle_root_pubkey,
@@ -63,7 +69,7 @@ pub fn process_initialize_epoch_reward_merkle_root(
         payer,
         epoch_reward_merkle_root,
         system_program,
-        &restaking_program,
+        program_id,
         &Rent::get()?,
         EpochRewardMerkleRoot::size(),
         &epoch_reward_merkle_root_seeds,
@@ -73,7 +79,12 @@ pub fn process_initialize_epoch_reward_merkle_root(
     epoch_reward_merkle_root_data[0] = EpochRewardMerkleRoot::DISCRIMINATOR;
     let epoch_reward_merkle_root =
         EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
-    *epoch_reward_merkle_root = EpochRewardMerkleRoot::new(*ncn.key, ncn_epoch);
+    *epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::new(*ncn.key, ncn_epoch, epoch_start_slot, epoch_length);
+
+    let reward_config_data = reward_config.data.borrow();
+    let reward_config = RewardConfig::try_from_slice_unchecked(&reward_config_data)?;
+    epoch_reward_merkle_root.snapshot_config(reward_config);
 
     Ok(())
 }
@@ -1,20 +1,34 @@
-use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::{load_signer, load_system_account, load_system_program};
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{
+    create_account,
+    loader::{load_signer, load_system_account, load_system_program},
+};
 use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    epoch_reward_merkle_root_ticket::EpochRewardMerkleRootTicket,
+    token_table::StakeTable,
+};
+use jito_reward_sdk::error::RewardError;
 use jito_vault_program::ID as VAULT_PROGRAM_ID;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
 };
 
-use crate::restaking_helpers::check_ncn_vault_operator_active;
+use crate::{
+    restaking_helpers::check_ncn_vault_operator_delegation_active,
+    ticket_helpers::{create_ticket_vault_marker, get_vault_operator_stake},
+};
 
+/// Creates the operator's ticket for the epoch, seeded with the stake one vault delegates to the
+/// operator. Only possible during the epoch's stake snapshot window, so the stake read from
+/// the delegation reflects the start of the epoch.
 pub fn process_initialize_epoch_reward_merkle_root_ticket(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
-    let [vault_config_info, vault_info, ncn_info, operator_info, ncn_operator_state_info, ncn_vault_ticket_info, operator_vault_ticket_info, vault_ncn_ticket_info, vault_operator_delegation_info, ncn_vault_slasher_ticket_info, vault_ncn_slasher_ticket_info, epoch_reward_merkle_root, epoch_reward_merkle_root_ticket, slasher, admin, system_program] =
+    let [vault_config_info, vault_info, ncn_info, operator_info, ncn_operator_state_info, ncn_vault_ticket_info, operator_vault_ticket_info, vault_ncn_ticket_info, vault_operator_delegation_info, epoch_reward_merkle_root, epoch_reward_merkle_root_ticket, epoch_reward_ticket_vault_marker, admin, system_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -28,10 +42,11 @@ pub fn process_initialize_epoch_reward_merkle_root_ticket(
     load_signer(admin, true)?;
     load_system_program(system_program)?;
 
-    check_ncn_vault_operator_active(
+    let current_slot = Clock::get()?.slot;
+
+    check_ncn_vault_operator_delegation_active(
         &restaking_program,
         &vault_program,
-        slasher,
         vault_config_info,
         vault_info,
         ncn_info,
@@ -41,9 +56,103 @@ pub fn process_initialize_epoch_reward_merkle_root_ticket(
         operator_vault_ticket_info,
         vault_ncn_ticket_info,
         vault_operator_delegation_info,
-        ncn_vault_slasher_ticket_info,
-        vault_ncn_slasher_ticket_info,
+        current_slot,
+    )?;
+
+    EpochRewardMerkleRoot::load(program_id, ncn_info.key, epoch_reward_merkle_root, true)?;
+    let ncn_epoch = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+
+        // Tickets register their stake, which is frozen once voting opens
+        if epoch_reward_merkle_root.state()? != EpochRewardState::AcceptingDeposits {
+            msg!(
+                "Epoch {} is no longer accepting deposits",
+                u64::from(epoch_reward_merkle_root.ncn_epoch)
+            );
+            return Err(RewardError::EpochNotAcceptingDeposits.into());
+        }
+
+        if !epoch_reward_merkle_root.is_stake_snapshot_open(current_slot) {
+            msg!(
+                "Tickets for epoch {} can no longer be created",
+                u64::from(epoch_reward_merkle_root.ncn_epoch)
+            );
+            return Err(RewardError::StakeSnapshotClosed.into());
+        }
+
+        u64::from(epoch_reward_merkle_root.ncn_epoch)
+    };
+
+    let (
+        epoch_reward_merkle_root_ticket_pubkey,
+        epoch_reward_merkle_root_ticket_bump,
+        mut epoch_reward_merkle_root_ticket_seeds,
+    ) = EpochRewardMerkleRootTicket::find_program_address(
+        program_id,
+        ncn_info.key,
+        operator_info.key,
+        ncn_epoch,
+    );
+    epoch_reward_merkle_root_ticket_seeds.push(vec![epoch_reward_merkle_root_ticket_bump]);
+
+    if epoch_reward_merkle_root_ticket_pubkey.ne(epoch_reward_merkle_root_ticket.key) {
+        msg!("Reward Merkle Root Ticket account is not at the correct PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The stake is read from the vault program, operators cannot self-report it
+    let (supported_mint, staked_amount) =
+        get_vault_operator_stake(vault_info, vault_operator_delegation_info)?;
+    let mut stake_table = StakeTable::new();
+    stake_table.set_value(&supported_mint, staked_amount)?;
+
+    msg!(
+        "Initializing epoch reward merkle root ticket (epoch {}) for operator {} at address {}",
+        ncn_epoch,
+        operator_info.key,
+        epoch_reward_merkle_root_ticket_pubkey
+    );
+
+    create_account(
+        admin,
+        epoch_reward_merkle_root_ticket,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        EpochRewardMerkleRootTicket::size(),
+        &epoch_reward_merkle_root_ticket_seeds,
+    )?;
+
+    let mut epoch_reward_merkle_root_ticket_data =
+        epoch_reward_merkle_root_ticket.try_borrow_mut_data()?;
+    epoch_reward_merkle_root_ticket_data[0] = EpochRewardMerkleRootTicket::DISCRIMINATOR;
+    let epoch_reward_merkle_root_ticket =
+        EpochRewardMerkleRootTicket::try_from_slice_unchecked_mut(
+            &mut epoch_reward_merkle_root_ticket_data,
+        )?;
+    *epoch_reward_merkle_root_ticket =
+        EpochRewardMerkleRootTicket::new(*ncn_info.key, *operator_info.key, ncn_epoch, stake_table);
+    drop(epoch_reward_merkle_root_ticket_data);
+
+    {
+        let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+        let epoch_reward_merkle_root = EpochRewardMerkleRoot::try_from_slice_unchecked_mut(
+            &mut epoch_reward_merkle_root_data,
+        )?;
+        epoch_reward_merkle_root.register_stake(&supported_mint, staked_amount)?;
+    }
+
+    // The initial vault is counted here, so it cannot be added again with UpdateTicketStake
+    create_ticket_vault_marker(
+        program_id,
+        &epoch_reward_merkle_root_ticket_pubkey,
+        vault_info.key,
+        epoch_reward_ticket_vault_marker,
+        admin,
+        system_program,
     )?;
 
-    todo!();
+    Ok(())
 }
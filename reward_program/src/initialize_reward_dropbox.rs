@@ -0,0 +1,105 @@
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{
+    create_account,
+    loader::{load_signer, load_system_account, load_system_program},
+};
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{reward_config::RewardConfig, reward_dropbox::RewardDropbox};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use crate::token_helpers::{load_associated_token_program, load_token_program};
+
+/// Creates the NCN's reward dropbox and an ATA for each (mint, dropbox_token_account,
+/// token_program) triplet in the remaining accounts. ATAs for new mints can be created
+/// later through the associated token program directly.
+pub fn process_initialize_reward_dropbox(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [reward_config, ncn, reward_dropbox, payer, associated_token_program, system_program, token_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_system_account(reward_dropbox, true)?;
+    load_signer(payer, true)?;
+    load_system_program(system_program)?;
+    load_associated_token_program(associated_token_program)?;
+
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+
+    if token_accounts.len() % 3 != 0 {
+        msg!("Token accounts must be passed as (mint, token account, token program) triplets");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let (reward_dropbox_pubkey, reward_dropbox_bump, mut reward_dropbox_seeds) =
+        RewardDropbox::find_program_address(program_id, ncn.key);
+    reward_dropbox_seeds.push(vec![reward_dropbox_bump]);
+
+    if reward_dropbox_pubkey.ne(reward_dropbox.key) {
+        msg!("Reward dropbox account is not at the correct PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    msg!(
+        "Initializing reward dropbox at address {}",
+        reward_dropbox.key
+    );
+    create_account(
+        payer,
+        reward_dropbox,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        RewardDropbox::size(),
+        &reward_dropbox_seeds,
+    )?;
+
+    {
+        let mut reward_dropbox_data = reward_dropbox.try_borrow_mut_data()?;
+        reward_dropbox_data[0] = RewardDropbox::DISCRIMINATOR;
+        let reward_dropbox = RewardDropbox::try_from_slice_unchecked_mut(&mut reward_dropbox_data)?;
+        *reward_dropbox = RewardDropbox::new(*ncn.key, reward_dropbox_bump);
+    }
+
+    let mut remaining = token_accounts;
+    while let [mint, reward_dropbox_token_account, token_program, rest @ ..] = remaining {
+        remaining = rest;
+
+        load_token_program(token_program)?;
+
+        msg!(
+            "Creating reward dropbox token account for mint {}",
+            mint.key
+        );
+        invoke(
+            &create_associated_token_account_idempotent(
+                payer.key,
+                reward_dropbox.key,
+                mint.key,
+                token_program.key,
+            ),
+            &[
+                payer.clone(),
+                reward_dropbox_token_account.clone(),
+                reward_dropbox.clone(),
+                mint.clone(),
+                system_program.clone(),
+                token_program.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,14 +1,26 @@
+mod accept_admin;
 mod close_marker_accounts;
+mod config_helpers;
 mod delinquent_to_latest;
 mod deposit_rewards;
 mod distribute_crank;
 mod dropbox_to_latest;
+mod finalize_consensus;
 mod initialize_config;
 mod initialize_epoch_reward_merkle_root;
 mod initialize_epoch_reward_merkle_root_ticket;
+mod initialize_reward_dropbox;
 mod restaking_helpers;
+mod set_fees;
+mod set_new_admin;
+mod set_paused;
+mod set_secondary_admin;
 mod slash;
 mod submit_ticket;
+mod sweep_to_latest;
+mod ticket_helpers;
+mod token_helpers;
+mod update_config;
 mod update_ticket_stake;
 
 use borsh::BorshDeserialize;
@@ -22,13 +34,25 @@ use solana_program::{
 use solana_security_txt::security_txt;
 
 use crate::{
+    accept_admin::process_accept_admin,
     close_marker_accounts::process_close_marker_accounts,
-    delinquent_to_latest::process_delinquent_to_latest, deposit_rewards::process_deposit_rewards,
-    distribute_crank::process_distribute_crank, dropbox_to_latest::process_dropbox_to_latest,
+    delinquent_to_latest::process_delinquent_to_latest,
+    deposit_rewards::{process_deposit_rewards, process_deposit_sol_rewards},
+    distribute_crank::{process_distribute_crank, process_distribute_sol_crank},
+    dropbox_to_latest::process_dropbox_to_latest,
+    finalize_consensus::process_finalize_consensus,
     initialize_config::process_initialize_config,
     initialize_epoch_reward_merkle_root::process_initialize_epoch_reward_merkle_root,
     initialize_epoch_reward_merkle_root_ticket::process_initialize_epoch_reward_merkle_root_ticket,
-    slash::process_slash, submit_ticket::process_submit_ticket,
+    initialize_reward_dropbox::process_initialize_reward_dropbox,
+    set_fees::process_set_fees,
+    set_new_admin::process_set_new_admin,
+    set_paused::process_set_paused,
+    set_secondary_admin::process_set_secondary_admin,
+    slash::process_slash,
+    submit_ticket::process_submit_ticket,
+    sweep_to_latest::process_sweep_to_latest,
+    update_config::process_update_config,
     update_ticket_stake::process_update_ticket_stake,
 };
 
@@ -67,6 +91,7 @@ pub fn process_instruction(
         RewardInstruction::InitializeConfig {
             valid_voting_slots,
             slots_before_closing_marker_accounts,
+            slash_amount,
         } => {
             msg!("Instruction: InitializeConfig");
             process_initialize_config(
@@ -74,6 +99,7 @@ pub fn process_instruction(
                 accounts,
                 valid_voting_slots,
                 slots_before_closing_marker_accounts,
+                slash_amount,
             )
         }
         RewardInstruction::InitializeEpochRewardMerkleRoot => {
@@ -84,21 +110,33 @@ pub fn process_instruction(
             msg!("Instruction: InitializeEpochRewardMerkleRootTicket");
             process_initialize_epoch_reward_merkle_root_ticket(program_id, accounts)
         }
+        RewardInstruction::InitializeRewardDropbox => {
+            msg!("Instruction: InitializeRewardDropbox");
+            process_initialize_reward_dropbox(program_id, accounts)
+        }
         // ------------------------------------------
         // Reward Operations
         // ------------------------------------------
-        RewardInstruction::DepositRewards => {
+        RewardInstruction::DepositRewards { amount } => {
             msg!("Instruction: DepositRewards");
-            process_deposit_rewards(program_id, accounts)
+            process_deposit_rewards(program_id, accounts, amount)
+        }
+        RewardInstruction::DepositSolRewards { amount } => {
+            msg!("Instruction: DepositSolRewards");
+            process_deposit_sol_rewards(program_id, accounts, amount)
         }
         RewardInstruction::UpdateTicketStake => {
-            msg!("Instruction: UploadAndVote");
+            msg!("Instruction: UpdateTicketStake");
             process_update_ticket_stake(program_id, accounts)
         }
         RewardInstruction::SubmitTicket { root } => {
-            msg!("Instruction: UploadAndVote");
+            msg!("Instruction: SubmitTicket");
             process_submit_ticket(program_id, accounts, root)
         }
+        RewardInstruction::FinalizeConsensus => {
+            msg!("Instruction: FinalizeConsensus");
+            process_finalize_consensus(program_id, accounts)
+        }
         RewardInstruction::Slash => {
             msg!("Instruction: Slash");
             process_slash(program_id, accounts)
@@ -111,13 +149,68 @@ pub fn process_instruction(
             msg!("Instruction: DelinquentToLatest");
             process_delinquent_to_latest(program_id, accounts)
         }
-        RewardInstruction::DistributeCrank => {
+        RewardInstruction::DistributeCrank { amount, proof } => {
             msg!("Instruction: DistributeCrank");
-            process_distribute_crank(program_id, accounts)
+            process_distribute_crank(program_id, accounts, amount, proof)
+        }
+        RewardInstruction::DistributeSolCrank { amount, proof } => {
+            msg!("Instruction: DistributeSolCrank");
+            process_distribute_sol_crank(program_id, accounts, amount, proof)
         }
         RewardInstruction::CloseMarkerAccounts => {
             msg!("Instruction: CloseMarkerAccounts");
             process_close_marker_accounts(program_id, accounts)
         }
+        RewardInstruction::SweepToLatest => {
+            msg!("Instruction: SweepToLatest");
+            process_sweep_to_latest(program_id, accounts)
+        }
+        // ------------------------------------------
+        // Config
+        // ------------------------------------------
+        RewardInstruction::UpdateConfig {
+            valid_voting_slots,
+            slots_before_closing_marker_accounts,
+            consensus_threshold,
+            min_participation,
+            stake_snapshot_slots,
+            slash_amount,
+            slashable_epochs,
+            claim_window_slots,
+        } => {
+            msg!("Instruction: UpdateConfig");
+            process_update_config(
+                program_id,
+                accounts,
+                valid_voting_slots,
+                slots_before_closing_marker_accounts,
+                consensus_threshold,
+                min_participation,
+                stake_snapshot_slots,
+                slash_amount,
+                slashable_epochs,
+                claim_window_slots,
+            )
+        }
+        RewardInstruction::SetNewAdmin => {
+            msg!("Instruction: SetNewAdmin");
+            process_set_new_admin(program_id, accounts)
+        }
+        RewardInstruction::AcceptAdmin => {
+            msg!("Instruction: AcceptAdmin");
+            process_accept_admin(program_id, accounts)
+        }
+        RewardInstruction::SetSecondaryAdmin { role } => {
+            msg!("Instruction: SetSecondaryAdmin");
+            process_set_secondary_admin(program_id, accounts, role)
+        }
+        RewardInstruction::SetPaused { paused } => {
+            msg!("Instruction: SetPaused");
+            process_set_paused(program_id, accounts, paused)
+        }
+        RewardInstruction::SetFees { fee_bps } => {
+            msg!("Instruction: SetFees");
+            process_set_fees(program_id, accounts, fee_bps)
+        }
     }
 }
@@ -6,25 +6,15 @@ use jito_restaking_core::{
     operator::Operator, operator_vault_ticket::OperatorVaultTicket,
 };
 use jito_vault_core::{
-    config::Config as VaultConfig, vault::Vault,
-    vault_ncn_slasher_operator_ticket::VaultNcnSlasherOperatorTicket,
-    vault_ncn_slasher_ticket::VaultNcnSlasherTicket, vault_ncn_ticket::VaultNcnTicket,
-    vault_operator_delegation::VaultOperatorDelegation,
+    config::Config as VaultConfig, vault::Vault, vault_ncn_slasher_ticket::VaultNcnSlasherTicket,
+    vault_ncn_ticket::VaultNcnTicket, vault_operator_delegation::VaultOperatorDelegation,
 };
 use jito_vault_sdk::error::VaultError;
 use solana_program::{
-    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
-    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
 };
 
-pub fn get_ncn_epoch_from_config(
-    slot: u64,
-    vault_config: &VaultConfig,
-) -> Result<u64, ProgramError> {
-    let epoch_length = vault_config.epoch_length();
-    get_ncn_epoch(slot, epoch_length)
-}
-
 pub fn get_ncn_epoch(slot: u64, epoch_length: u64) -> Result<u64, ProgramError> {
     let ncn_epoch = slot
         .checked_div(epoch_length)
@@ -32,11 +22,12 @@ pub fn get_ncn_epoch(slot: u64, epoch_length: u64) -> Result<u64, ProgramError>
     Ok(ncn_epoch)
 }
 
+/// Checks the operator has an active delegation from a vault of the NCN at `slot`. Does not
+/// need the slasher, see `check_ncn_vault_operator_active` for that.
 #[allow(clippy::too_many_arguments)]
-pub fn check_ncn_vault_operator_active(
+pub fn check_ncn_vault_operator_delegation_active(
     restaking_program: &Pubkey,
     vault_program: &Pubkey,
-    slasher: &AccountInfo,
     vault_config_info: &AccountInfo,
     vault_info: &AccountInfo,
     ncn_info: &AccountInfo,
@@ -46,8 +37,7 @@ pub fn check_ncn_vault_operator_active(
     operator_vault_ticket_info: &AccountInfo,
     vault_ncn_ticket_info: &AccountInfo,
     vault_operator_delegation_info: &AccountInfo,
-    ncn_vault_slasher_ticket_info: &AccountInfo,
-    vault_ncn_slasher_ticket_info: &AccountInfo,
+    slot: u64,
 ) -> ProgramResult {
     // Load the vault config
     VaultConfig::load(vault_program, vault_config_info, false)?;
@@ -55,9 +45,7 @@ pub fn check_ncn_vault_operator_active(
     let vault_config = VaultConfig::try_from_slice_unchecked(&vault_config_data)?;
 
     // Load vault
-    Vault::load(vault_program, vault_info, true)?;
-    let mut vault_data = vault_info.data.borrow_mut();
-    let vault = Vault::try_from_slice_unchecked_mut(&mut vault_data)?;
+    Vault::load(vault_program, vault_info, false)?;
 
     // Load NCN
     Ncn::load(restaking_program, ncn_info, false)?;
@@ -111,6 +99,62 @@ pub fn check_ncn_vault_operator_active(
     let vault_ncn_ticket = VaultNcnTicket::try_from_slice_unchecked(&vault_ncn_ticket_data)?;
 
     // Load Vault Operator Delegation
+    VaultOperatorDelegation::load(
+        vault_program,
+        vault_operator_delegation_info,
+        vault_info,
+        operator_info,
+        false,
+    )?;
+
+    check_delegation_states_active_or_cooling_down(
+        ncn_operator_state,
+        operator_vault_ticket,
+        vault_ncn_ticket,
+        ncn_vault_ticket,
+        slot,
+        vault_config.epoch_length(),
+    )
+}
+
+/// Checks the operator had an active delegation from a vault of the NCN at `epoch_slot`, a slot
+/// of the epoch being slashed, and that `slasher` can slash it at `current_slot`
+#[allow(clippy::too_many_arguments)]
+pub fn check_ncn_vault_operator_active(
+    restaking_program: &Pubkey,
+    vault_program: &Pubkey,
+    slasher: &AccountInfo,
+    vault_config_info: &AccountInfo,
+    vault_info: &AccountInfo,
+    ncn_info: &AccountInfo,
+    operator_info: &AccountInfo,
+    ncn_operator_state_info: &AccountInfo,
+    ncn_vault_ticket_info: &AccountInfo,
+    operator_vault_ticket_info: &AccountInfo,
+    vault_ncn_ticket_info: &AccountInfo,
+    vault_operator_delegation_info: &AccountInfo,
+    ncn_vault_slasher_ticket_info: &AccountInfo,
+    vault_ncn_slasher_ticket_info: &AccountInfo,
+    epoch_slot: u64,
+    current_slot: u64,
+) -> ProgramResult {
+    check_ncn_vault_operator_delegation_active(
+        restaking_program,
+        vault_program,
+        vault_config_info,
+        vault_info,
+        ncn_info,
+        operator_info,
+        ncn_operator_state_info,
+        ncn_vault_ticket_info,
+        operator_vault_ticket_info,
+        vault_ncn_ticket_info,
+        vault_operator_delegation_info,
+        epoch_slot,
+    )?;
+
+    // The slash writes to the vault and the delegation
+    Vault::load(vault_program, vault_info, true)?;
     VaultOperatorDelegation::load(
         vault_program,
         vault_operator_delegation_info,
@@ -118,9 +162,9 @@ pub fn check_ncn_vault_operator_active(
         operator_info,
         true,
     )?;
-    let mut vault_operator_delegation_data = vault_operator_delegation_info.data.borrow_mut();
-    let vault_operator_delegation =
-        VaultOperatorDelegation::try_from_slice_unchecked_mut(&mut vault_operator_delegation_data)?;
+
+    let vault_config_data = vault_config_info.data.borrow();
+    let vault_config = VaultConfig::try_from_slice_unchecked(&vault_config_data)?;
 
     // Load slasher
     load_signer(slasher, false)?;
@@ -151,30 +195,15 @@ pub fn check_ncn_vault_operator_active(
     let vault_ncn_slasher_ticket =
         VaultNcnSlasherTicket::try_from_slice_unchecked(&vault_ncn_slasher_ticket_data)?;
 
-    let current_slot = Clock::get()?.slot;
-    let epoch_length = vault_config.epoch_length();
-
-    // All ticket states shall be active or cooling down
-    check_states_active_or_cooling_down(
-        ncn_operator_state,
-        operator_vault_ticket,
-        vault_ncn_ticket,
-        ncn_vault_ticket,
+    check_slasher_states_active_or_cooling_down(
         vault_ncn_slasher_ticket,
         ncn_vault_slasher_ticket,
         current_slot,
-        epoch_length,
-    )?;
-
-    Ok(())
+        vault_config.epoch_length(),
+    )
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn check_states_active_or_cooling_down(
-    ncn_operator_state: &NcnOperatorState,
-    operator_vault_ticket: &OperatorVaultTicket,
-    vault_ncn_ticket: &VaultNcnTicket,
-    ncn_vault_ticket: &NcnVaultTicket,
+pub fn check_slasher_states_active_or_cooling_down(
     vault_ncn_slasher_ticket: &VaultNcnSlasherTicket,
     ncn_vault_slasher_ticket: &NcnVaultSlasherTicket,
     slot: u64,
@@ -194,6 +223,17 @@ pub fn check_states_active_or_cooling_down(
         msg!("NCN vault slasher ticket is not active or in cooldown");
         return Err(VaultError::NcnVaultSlasherTicketUnslashable.into());
     }
+    Ok(())
+}
+
+pub fn check_delegation_states_active_or_cooling_down(
+    ncn_operator_state: &NcnOperatorState,
+    operator_vault_ticket: &OperatorVaultTicket,
+    vault_ncn_ticket: &VaultNcnTicket,
+    ncn_vault_ticket: &NcnVaultTicket,
+    slot: u64,
+    epoch_length: u64,
+) -> ProgramResult {
     if !ncn_operator_state
         .ncn_opt_in_state
         .is_active_or_cooldown(slot, epoch_length)
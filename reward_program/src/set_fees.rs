@@ -0,0 +1,44 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::reward_config::RewardConfig;
+use jito_reward_sdk::error::RewardError;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Sets the fee and the wallet it is paid to. Epochs copy both when they are initialized, so
+/// the change only applies to later epochs. Only the fee authority can call it, so the fee
+/// stays unset until the admin assigns one.
+pub fn process_set_fees(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee_bps: u64,
+) -> ProgramResult {
+    let [config, ncn, fee_authority, fee_wallet] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(fee_authority, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, config, true)?;
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = RewardConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    if !RewardConfig::is_role_holder(&config.fee_authority, fee_authority.key) {
+        msg!("Incorrect reward config fee authority");
+        return Err(RewardError::IncorrectFeeAuthority.into());
+    }
+
+    msg!("Setting fee to {} bps paid to {}", fee_bps, fee_wallet.key);
+    config.set_fee_bps(fee_bps)?;
+    config.fee_wallet = *fee_wallet.key;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::reward_config::RewardConfig;
+use jito_reward_sdk::error::RewardError;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Starts transferring the reward config admin role to `new_admin`.
+/// The current admin keeps the role until `new_admin` accepts it.
+pub fn process_set_new_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [config, ncn, admin, new_admin] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(admin, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, config, true)?;
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = RewardConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    if config.admin.ne(admin.key) {
+        msg!("Incorrect reward config admin");
+        return Err(RewardError::IncorrectConfigAdmin.into());
+    }
+
+    if new_admin.key.eq(&Pubkey::default()) {
+        msg!("New admin cannot be the default pubkey");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    msg!("Setting pending admin to {}", new_admin.key);
+    config.set_pending_admin(new_admin.key);
+
+    Ok(())
+}
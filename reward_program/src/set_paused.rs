@@ -0,0 +1,43 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::reward_config::RewardConfig;
+use jito_reward_sdk::error::RewardError;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Pauses or resumes deposits, dropbox sweeps, distribution, rollovers and finalizing before
+/// the voting window ends, see `check_not_paused` for the full scope. Only the pause authority
+/// can call it, so nothing can be paused until the admin assigns one.
+pub fn process_set_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused: bool,
+) -> ProgramResult {
+    let [config, ncn, pause_authority] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(pause_authority, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, config, true)?;
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = RewardConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    if !RewardConfig::is_role_holder(&config.pause_authority, pause_authority.key) {
+        msg!("Incorrect reward config pause authority");
+        return Err(RewardError::IncorrectPauseAuthority.into());
+    }
+
+    msg!("Setting paused to {}", paused);
+    config.set_paused(paused);
+
+    Ok(())
+}
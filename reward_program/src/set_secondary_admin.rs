@@ -0,0 +1,50 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::reward_config::RewardConfig;
+use jito_reward_sdk::{error::RewardError, instruction::RewardAdminRole};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Assigns one of the optional roles to `new_admin`. Passing the system program, whose
+/// address is the default pubkey, unsets the role.
+pub fn process_set_secondary_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    role: RewardAdminRole,
+) -> ProgramResult {
+    let [config, ncn, admin, new_admin] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(admin, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, config, true)?;
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = RewardConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    if config.admin.ne(admin.key) {
+        msg!("Incorrect reward config admin");
+        return Err(RewardError::IncorrectConfigAdmin.into());
+    }
+
+    match role {
+        RewardAdminRole::PauseAuthority => {
+            msg!("Setting pause authority to {}", new_admin.key);
+            config.pause_authority = *new_admin.key;
+        }
+        RewardAdminRole::FeeAuthority => {
+            msg!("Setting fee authority to {}", new_admin.key);
+            config.fee_authority = *new_admin.key;
+        }
+    }
+
+    Ok(())
+}
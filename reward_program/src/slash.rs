@@ -1,13 +1,411 @@
-use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{create_account, loader::load_system_account};
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    epoch_reward_merkle_root_ticket::EpochRewardMerkleRootTicket,
+    epoch_reward_slash_marker::EpochRewardSlashMarker,
+    merkle_root::MerkleRoot,
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
+use jito_vault_program::ID as VAULT_PROGRAM_ID;
+use jito_vault_sdk::sdk::slash;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_program, sysvar::Sysvar,
 };
 
+use crate::restaking_helpers::{check_ncn_vault_operator_active, get_ncn_epoch};
+
+/// Slashes an operator's delegation from one vault once voting for an epoch is done.
+/// An operator is slashable if there was no consensus, if they did not vote, or if they
+/// voted for a root other than the consensus root. The epoch must have left voting, by being
+/// finalized or rolled over, so the outcome can no longer change. Each delegation can only be
+/// slashed once per epoch, which is recorded in an EpochRewardSlashMarker. Slashing is not
+/// affected by the pause, so the pause authority cannot shield operators from it.
+/// The delegation must have been active when the epoch started, and only epochs within the
+/// config's `slashable_epochs` of the current epoch can be slashed.
 pub fn process_slash(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    // Add code here
+    let [reward_config, vault_config_info, vault_info, ncn_info, operator_info, ncn_operator_state_info, ncn_vault_ticket_info, operator_vault_ticket_info, vault_ncn_ticket_info, vault_operator_delegation_info, ncn_vault_slasher_ticket_info, vault_ncn_slasher_ticket_info, vault_ncn_slasher_operator_ticket_info, vault_token_account, slasher_token_account, epoch_reward_merkle_root, epoch_reward_merkle_root_ticket, slasher, vault_program_info, token_program, epoch_reward_slash_marker, payer, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+    let vault_program = VAULT_PROGRAM_ID;
+
+    // Account Checks
+    if vault_program_info.key.ne(&vault_program) {
+        msg!("Account is not the vault program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    RewardConfig::load(program_id, ncn_info.key, reward_config, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn_info.key, epoch_reward_merkle_root, false)?;
+
+    let current_slot = Clock::get()?.slot;
+
+    let (ncn_epoch, epoch_start_slot, consensus) = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+        let ncn_epoch = u64::from(epoch_reward_merkle_root.ncn_epoch);
+
+        if matches!(
+            epoch_reward_merkle_root.state()?,
+            EpochRewardState::AcceptingDeposits | EpochRewardState::Voting
+        ) {
+            msg!("Voting for epoch {} is not finalized", ncn_epoch);
+            return Err(RewardError::EpochNotFinalized.into());
+        }
+
+        let current_epoch = get_ncn_epoch(current_slot, epoch_reward_merkle_root.epoch_length())?;
+        let reward_config_data = reward_config.data.borrow();
+        let reward_config = RewardConfig::try_from_slice_unchecked(&reward_config_data)?;
+        if !reward_config.is_slashable_epoch(ncn_epoch, current_epoch) {
+            msg!(
+                "Epoch {} is too old to be slashed in epoch {}",
+                ncn_epoch,
+                current_epoch
+            );
+            return Err(RewardError::SlashWindowClosed.into());
+        }
+
+        (
+            ncn_epoch,
+            epoch_reward_merkle_root.epoch_start_slot(),
+            epoch_reward_merkle_root.consensus(),
+        )
+    };
+
+    // An operator that was not delegated to when the epoch started had no part in its vote
+    check_ncn_vault_operator_active(
+        &restaking_program,
+        &vault_program,
+        slasher,
+        vault_config_info,
+        vault_info,
+        ncn_info,
+        operator_info,
+        ncn_operator_state_info,
+        ncn_vault_ticket_info,
+        operator_vault_ticket_info,
+        vault_ncn_ticket_info,
+        vault_operator_delegation_info,
+        ncn_vault_slasher_ticket_info,
+        vault_ncn_slasher_ticket_info,
+        epoch_start_slot,
+        current_slot,
+    )?;
+
+    let vote = get_operator_vote(
+        program_id,
+        ncn_info.key,
+        operator_info.key,
+        ncn_epoch,
+        epoch_reward_merkle_root_ticket,
+    )?;
+
+    match (consensus, vote) {
+        (None, _) => msg!("Epoch {} did not reach consensus", ncn_epoch),
+        (Some(_), None) => msg!("Operator did not vote in epoch {}", ncn_epoch),
+        (Some(consensus), Some(root)) if root.ne(&consensus.root) => {
+            msg!("Operator voted for a losing root in epoch {}", ncn_epoch)
+        }
+        (Some(_), Some(_)) => {
+            msg!(
+                "Operator voted for the consensus root in epoch {}",
+                ncn_epoch
+            );
+            return Err(RewardError::OperatorNotSlashable.into());
+        }
+    }
+
+    create_slash_marker(
+        program_id,
+        epoch_reward_merkle_root.key,
+        operator_info.key,
+        vault_info.key,
+        epoch_reward_slash_marker,
+        payer,
+        system_program,
+    )?;
+
+    let slash_amount = {
+        let reward_config_data = reward_config.data.borrow();
+        let reward_config = RewardConfig::try_from_slice_unchecked(&reward_config_data)?;
+        u64::from(reward_config.slash_amount)
+    };
+
+    msg!(
+        "Slashing {} from operator {} in vault {}",
+        slash_amount,
+        operator_info.key,
+        vault_info.key
+    );
+
+    invoke(
+        &slash(
+            &vault_program,
+            vault_config_info.key,
+            vault_info.key,
+            ncn_info.key,
+            operator_info.key,
+            slasher.key,
+            ncn_operator_state_info.key,
+            ncn_vault_ticket_info.key,
+            operator_vault_ticket_info.key,
+            vault_ncn_ticket_info.key,
+            vault_operator_delegation_info.key,
+            ncn_vault_slasher_ticket_info.key,
+            vault_ncn_slasher_ticket_info.key,
+            vault_ncn_slasher_operator_ticket_info.key,
+            vault_token_account.key,
+            slasher_token_account.key,
+            slash_amount,
+        ),
+        &[
+            vault_config_info.clone(),
+            vault_info.clone(),
+            ncn_info.clone(),
+            operator_info.clone(),
+            slasher.clone(),
+            ncn_operator_state_info.clone(),
+            ncn_vault_ticket_info.clone(),
+            operator_vault_ticket_info.clone(),
+            vault_ncn_ticket_info.clone(),
+            vault_operator_delegation_info.clone(),
+            ncn_vault_slasher_ticket_info.clone(),
+            vault_ncn_slasher_ticket_info.clone(),
+            vault_ncn_slasher_operator_ticket_info.clone(),
+            vault_token_account.clone(),
+            slasher_token_account.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Creates the marker recording that the operator's delegation from the vault has been slashed
+/// for the epoch. Fails if it was already slashed.
+fn create_slash_marker<'a, 'info>(
+    program_id: &Pubkey,
+    epoch_reward_merkle_root: &Pubkey,
+    operator: &Pubkey,
+    vault: &Pubkey,
+    epoch_reward_slash_marker: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+) -> ProgramResult {
+    check_not_slashed(
+        program_id,
+        epoch_reward_merkle_root,
+        operator,
+        vault,
+        epoch_reward_slash_marker,
+    )?;
+    load_system_account(epoch_reward_slash_marker, true)?;
+
+    let (_, marker_bump, mut marker_seeds) = EpochRewardSlashMarker::find_program_address(
+        program_id,
+        epoch_reward_merkle_root,
+        operator,
+        vault,
+    );
+    marker_seeds.push(vec![marker_bump]);
+
+    create_account(
+        payer,
+        epoch_reward_slash_marker,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        EpochRewardSlashMarker::size(),
+        &marker_seeds,
+    )?;
+
+    let mut marker_data = epoch_reward_slash_marker.try_borrow_mut_data()?;
+    marker_data[0] = EpochRewardSlashMarker::DISCRIMINATOR;
+    let marker = EpochRewardSlashMarker::try_from_slice_unchecked_mut(&mut marker_data)?;
+    *marker = EpochRewardSlashMarker::new(*epoch_reward_merkle_root, *operator, *vault);
+
+    Ok(())
+}
+
+/// Checks that the slash marker is at the correct PDA and has not been created yet
+fn check_not_slashed(
+    program_id: &Pubkey,
+    epoch_reward_merkle_root: &Pubkey,
+    operator: &Pubkey,
+    vault: &Pubkey,
+    epoch_reward_slash_marker: &AccountInfo,
+) -> ProgramResult {
+    let (marker_pubkey, _, _) = EpochRewardSlashMarker::find_program_address(
+        program_id,
+        epoch_reward_merkle_root,
+        operator,
+        vault,
+    );
+
+    if marker_pubkey.ne(epoch_reward_slash_marker.key) {
+        msg!("Slash marker account is not at the correct PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if epoch_reward_slash_marker.owner.ne(&system_program::id())
+        || !epoch_reward_slash_marker.data_is_empty()
+    {
+        msg!(
+            "Operator {} has already been slashed in vault {} for this epoch",
+            operator,
+            vault
+        );
+        return Err(RewardError::OperatorAlreadySlashed.into());
+    }
+
+    Ok(())
+}
+
+/// Returns the root the operator voted for, or `None` if they never created a ticket
+/// or never voted with it
+fn get_operator_vote(
+    program_id: &Pubkey,
+    ncn: &Pubkey,
+    operator: &Pubkey,
+    ncn_epoch: u64,
+    epoch_reward_merkle_root_ticket: &AccountInfo,
+) -> Result<Option<MerkleRoot>, ProgramError> {
+    if epoch_reward_merkle_root_ticket
+        .owner
+        .eq(&system_program::id())
+        && epoch_reward_merkle_root_ticket.data_is_empty()
+    {
+        let (epoch_reward_merkle_root_ticket_pubkey, _, _) =
+            EpochRewardMerkleRootTicket::find_program_address(program_id, ncn, operator, ncn_epoch);
+
+        if epoch_reward_merkle_root_ticket_pubkey.ne(epoch_reward_merkle_root_ticket.key) {
+            msg!("Epoch reward merkle root ticket account is not at the correct PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        return Ok(None);
+    }
+
+    EpochRewardMerkleRootTicket::load(
+        program_id,
+        ncn,
+        operator,
+        ncn_epoch,
+        epoch_reward_merkle_root_ticket,
+        false,
+    )?;
+
+    let epoch_reward_merkle_root_ticket_data = epoch_reward_merkle_root_ticket.data.borrow();
+    let epoch_reward_merkle_root_ticket = EpochRewardMerkleRootTicket::try_from_slice_unchecked(
+        &epoch_reward_merkle_root_ticket_data,
+    )?;
+
+    if !epoch_reward_merkle_root_ticket.has_voted() {
+        return Ok(None);
+    }
+
+    Ok(Some(epoch_reward_merkle_root_ticket.root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_not_slashed() {
+        let program_id = Pubkey::new_unique();
+        let epoch_reward_merkle_root = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let (marker_pubkey, _, _) = EpochRewardSlashMarker::find_program_address(
+            &program_id,
+            &epoch_reward_merkle_root,
+            &operator,
+            &vault,
+        );
+
+        let system_program_id = system_program::id();
+        let mut lamports = 0;
+        let mut data = [];
+        let marker = AccountInfo::new(
+            &marker_pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program_id,
+            false,
+            0,
+        );
+        assert_eq!(
+            check_not_slashed(
+                &program_id,
+                &epoch_reward_merkle_root,
+                &operator,
+                &vault,
+                &marker
+            ),
+            Ok(())
+        );
+
+        // The marker is per vault
+        assert_eq!(
+            check_not_slashed(
+                &program_id,
+                &epoch_reward_merkle_root,
+                &operator,
+                &Pubkey::new_unique(),
+                &marker
+            ),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_check_not_slashed_twice() {
+        let program_id = Pubkey::new_unique();
+        let epoch_reward_merkle_root = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let (marker_pubkey, _, _) = EpochRewardSlashMarker::find_program_address(
+            &program_id,
+            &epoch_reward_merkle_root,
+            &operator,
+            &vault,
+        );
+
+        // The marker created by the first slash
+        let mut lamports = 1_000_000;
+        let mut data = vec![0; EpochRewardSlashMarker::size() as usize];
+        data[0] = EpochRewardSlashMarker::DISCRIMINATOR;
+        let marker = AccountInfo::new(
+            &marker_pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
 
-    todo!();
+        assert_eq!(
+            check_not_slashed(
+                &program_id,
+                &epoch_reward_merkle_root,
+                &operator,
+                &vault,
+                &marker
+            ),
+            Err(RewardError::OperatorAlreadySlashed.into())
+        );
+    }
 }
@@ -1,18 +1,112 @@
 use jito_bytemuck::AccountDeserialize;
 use jito_jsm_core::loader::load_signer;
-use jito_reward_core::merkle_root::MerkleRoot;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_restaking_core::{ncn::Ncn, operator::Operator};
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    epoch_reward_merkle_root_ticket::EpochRewardMerkleRootTicket,
+    merkle_root::MerkleRoot,
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
 };
 
+/// Casts the operator's vote for `root`. If the operator already voted, their
+/// stake is moved from the previous root to the new one. Voting opens once the epoch ends.
 pub fn process_submit_ticket(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     root: MerkleRoot,
 ) -> ProgramResult {
-    // Add code here
+    let [reward_config, ncn, operator, epoch_reward_merkle_root, epoch_reward_merkle_root_ticket, operator_admin] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(operator_admin, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    Operator::load(&restaking_program, operator, false)?;
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn.key, epoch_reward_merkle_root, true)?;
+
+    let ncn_epoch = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+        u64::from(epoch_reward_merkle_root.ncn_epoch)
+    };
+    EpochRewardMerkleRootTicket::load(
+        program_id,
+        ncn.key,
+        operator.key,
+        ncn_epoch,
+        epoch_reward_merkle_root_ticket,
+        true,
+    )?;
+
+    {
+        let operator_data = operator.data.borrow();
+        let operator = Operator::try_from_slice_unchecked(&operator_data)?;
+        if operator.admin.ne(operator_admin.key) {
+            msg!("Incorrect operator admin");
+            return Err(RewardError::IncorrectOperatorAdmin.into());
+        }
+    }
+
+    if root.is_empty() {
+        msg!("Cannot vote for an empty merkle root");
+        return Err(RewardError::InvalidMerkleRoot.into());
+    }
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+
+    let current_slot = Clock::get()?.slot;
+
+    let valid_voting_slots = epoch_reward_merkle_root.valid_voting_slots();
+    if epoch_reward_merkle_root.is_voting_done(current_slot, valid_voting_slots) {
+        msg!("Voting for epoch {} is over", ncn_epoch);
+        return Err(RewardError::VotingIsOver.into());
+    }
+
+    epoch_reward_merkle_root.open_voting(current_slot)?;
+    match epoch_reward_merkle_root.state()? {
+        EpochRewardState::Voting => {}
+        EpochRewardState::AcceptingDeposits => {
+            msg!("Voting for epoch {} starts after the epoch ends", ncn_epoch);
+            return Err(RewardError::VotingNotStarted.into());
+        }
+        _ => {
+            msg!("Voting for epoch {} is closed", ncn_epoch);
+            return Err(RewardError::EpochNotVoting.into());
+        }
+    }
+
+    let mut epoch_reward_merkle_root_ticket_data =
+        epoch_reward_merkle_root_ticket.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root_ticket =
+        EpochRewardMerkleRootTicket::try_from_slice_unchecked_mut(
+            &mut epoch_reward_merkle_root_ticket_data,
+        )?;
+
+    if epoch_reward_merkle_root_ticket.has_voted() {
+        msg!("Operator {} is changing their vote", operator.key);
+        epoch_reward_merkle_root.remove_vote(
+            &epoch_reward_merkle_root_ticket.root,
+            &epoch_reward_merkle_root_ticket.stake_table,
+        )?;
+    }
+
+    epoch_reward_merkle_root.add_vote(&root, &epoch_reward_merkle_root_ticket.stake_table)?;
+    epoch_reward_merkle_root_ticket.root = root;
 
-    todo!();
+    Ok(())
 }
@@ -0,0 +1,113 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+use crate::{
+    config_helpers::check_not_paused,
+    delinquent_to_latest::{check_rollover_mints, move_rewards_to_latest},
+    deposit_rewards::check_deposit_epoch,
+};
+
+/// Ends the distribution of an epoch that reached consensus, moving whatever was not paid out
+/// into the current epoch. Allowed once nothing is left to distribute, or once
+/// `claim_window_slots` have passed after the voting window, which leaves
+/// dust, unclaimed leaves and deposits the tree does not cover to the next epoch.
+/// Token balances are passed as (mint, epoch_token_account, current_token_account,
+/// token_program) groups in the remaining accounts and every deposited mint must be included.
+pub fn process_sweep_to_latest(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [restaking_config, reward_config, ncn, epoch_reward_merkle_root, current_epoch_reward_merkle_root, token_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Account Checks
+    RewardConfig::load(program_id, ncn.key, reward_config, false)?;
+    check_not_paused(reward_config)?;
+    EpochRewardMerkleRoot::load(program_id, ncn.key, epoch_reward_merkle_root, true)?;
+    check_deposit_epoch(
+        program_id,
+        restaking_config,
+        ncn,
+        current_epoch_reward_merkle_root,
+    )?;
+
+    if epoch_reward_merkle_root
+        .key
+        .eq(current_epoch_reward_merkle_root.key)
+    {
+        msg!("Rewards cannot be swept into the same epoch");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if token_accounts.len() % 4 != 0 {
+        msg!("Token accounts must be passed in groups of (mint, epoch token account, current token account, token program)");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let current_slot = Clock::get()?.slot;
+
+    let ncn_epoch = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+        let ncn_epoch = u64::from(epoch_reward_merkle_root.ncn_epoch);
+
+        match epoch_reward_merkle_root.state()? {
+            EpochRewardState::Finalized | EpochRewardState::Distributing => {}
+            EpochRewardState::AcceptingDeposits | EpochRewardState::Voting => {
+                msg!("Voting for epoch {} is not finalized", ncn_epoch);
+                return Err(RewardError::EpochNotFinalized.into());
+            }
+            EpochRewardState::RolledOver => {
+                msg!("Rewards for epoch {} were rolled over", ncn_epoch);
+                return Err(RewardError::RolledOver.into());
+            }
+            EpochRewardState::Distributed => {
+                msg!("Epoch {} has no distribution to end", ncn_epoch);
+                return Err(RewardError::EpochNotDistributing.into());
+            }
+        }
+
+        if epoch_reward_merkle_root.consensus().is_none() {
+            msg!("Epoch {} did not reach consensus", ncn_epoch);
+            return Err(RewardError::ConsensusNotReached.into());
+        }
+
+        if !epoch_reward_merkle_root.can_finish_distribution(
+            current_slot,
+            epoch_reward_merkle_root.valid_voting_slots(),
+            epoch_reward_merkle_root.claim_window_slots(),
+        ) {
+            msg!("Rewards of epoch {} can still be claimed", ncn_epoch);
+            return Err(RewardError::ClaimWindowOpen.into());
+        }
+
+        check_rollover_mints(&epoch_reward_merkle_root.deposited_rewards, token_accounts)?;
+
+        ncn_epoch
+    };
+
+    move_rewards_to_latest(
+        program_id,
+        ncn.key,
+        ncn_epoch,
+        epoch_reward_merkle_root,
+        current_epoch_reward_merkle_root,
+        token_accounts,
+    )?;
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+    epoch_reward_merkle_root.finish_distribution(current_slot)?;
+
+    Ok(())
+}
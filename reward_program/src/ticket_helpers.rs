@@ -0,0 +1,79 @@
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{create_account, loader::load_system_account};
+use jito_reward_core::epoch_reward_ticket_vault_marker::EpochRewardTicketVaultMarker;
+use jito_reward_sdk::error::RewardError;
+use jito_vault_core::{vault::Vault, vault_operator_delegation::VaultOperatorDelegation};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey, rent::Rent, system_program, sysvar::Sysvar,
+};
+
+/// Returns the vault's supported mint and the amount the vault has staked to the operator.
+/// Both accounts are expected to have been loaded by the caller.
+pub fn get_vault_operator_stake(
+    vault_info: &AccountInfo,
+    vault_operator_delegation_info: &AccountInfo,
+) -> Result<(Pubkey, u64), ProgramError> {
+    let vault_data = vault_info.data.borrow();
+    let vault = Vault::try_from_slice_unchecked(&vault_data)?;
+
+    let vault_operator_delegation_data = vault_operator_delegation_info.data.borrow();
+    let vault_operator_delegation =
+        VaultOperatorDelegation::try_from_slice_unchecked(&vault_operator_delegation_data)?;
+
+    Ok((
+        vault.supported_mint,
+        vault_operator_delegation.delegation_state.staked_amount(),
+    ))
+}
+
+/// Creates the marker recording that the vault has been counted in the ticket.
+/// Fails if the vault was already counted.
+pub fn create_ticket_vault_marker<'a, 'info>(
+    program_id: &Pubkey,
+    epoch_reward_merkle_root_ticket: &Pubkey,
+    vault: &Pubkey,
+    epoch_reward_ticket_vault_marker: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+) -> ProgramResult {
+    let (marker_pubkey, marker_bump, mut marker_seeds) =
+        EpochRewardTicketVaultMarker::find_program_address(
+            program_id,
+            epoch_reward_merkle_root_ticket,
+            vault,
+        );
+    marker_seeds.push(vec![marker_bump]);
+
+    if marker_pubkey.ne(epoch_reward_ticket_vault_marker.key) {
+        msg!("Ticket vault marker account is not at the correct PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if epoch_reward_ticket_vault_marker
+        .owner
+        .ne(&system_program::id())
+        || !epoch_reward_ticket_vault_marker.data_is_empty()
+    {
+        msg!("Vault {} has already been counted for this ticket", vault);
+        return Err(RewardError::VaultAlreadyCounted.into());
+    }
+    load_system_account(epoch_reward_ticket_vault_marker, true)?;
+
+    create_account(
+        payer,
+        epoch_reward_ticket_vault_marker,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        EpochRewardTicketVaultMarker::size(),
+        &marker_seeds,
+    )?;
+
+    let mut marker_data = epoch_reward_ticket_vault_marker.try_borrow_mut_data()?;
+    marker_data[0] = EpochRewardTicketVaultMarker::DISCRIMINATOR;
+    let marker = EpochRewardTicketVaultMarker::try_from_slice_unchecked_mut(&mut marker_data)?;
+    *marker = EpochRewardTicketVaultMarker::new(*epoch_reward_merkle_root_ticket, *vault);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use jito_reward_sdk::error::RewardError;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::{Account, Mint},
+};
+
+/// Checks that the account is either the SPL Token or the Token-2022 program
+pub fn load_token_program(token_program: &AccountInfo) -> Result<(), ProgramError> {
+    if token_program.key.ne(&spl_token::id()) && token_program.key.ne(&spl_token_2022::id()) {
+        msg!("Token program is not SPL Token or Token-2022");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Checks that the account is the associated token account program
+pub fn load_associated_token_program(
+    associated_token_program: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if associated_token_program
+        .key
+        .ne(&spl_associated_token_account::id())
+    {
+        msg!("Account is not the associated token account program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Checks that the mint is owned by `token_program` and returns its decimals
+pub fn load_mint_decimals(token_program: &Pubkey, mint: &AccountInfo) -> Result<u8, ProgramError> {
+    if mint.owner.ne(token_program) {
+        msg!("Mint is not owned by the token program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mint_data = mint.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
+
+/// Checks that rewards of the mint can always be moved out of the program's token accounts and
+/// returns its decimals. A freeze authority, transfer hook or non-transferable mint could leave
+/// deposited rewards stuck in an epoch.
+pub fn load_reward_mint(token_program: &Pubkey, mint: &AccountInfo) -> Result<u8, ProgramError> {
+    if mint.owner.ne(token_program) {
+        msg!("Mint is not owned by the token program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mint_data = mint.data.borrow();
+    let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+
+    if mint_state.base.freeze_authority.is_some() {
+        msg!("Mint {} has a freeze authority", mint.key);
+        return Err(RewardError::UntransferableRewardMint.into());
+    }
+
+    let extension_types = mint_state.get_extension_types()?;
+    if extension_types.contains(&ExtensionType::TransferHook)
+        || extension_types.contains(&ExtensionType::NonTransferable)
+    {
+        msg!(
+            "Mint {} has a transfer hook or is non-transferable",
+            mint.key
+        );
+        return Err(RewardError::UntransferableRewardMint.into());
+    }
+
+    Ok(mint_state.base.decimals)
+}
+
+/// Checks that `token_account` is the associated token account of `owner` for `mint`
+pub fn load_associated_token_account(
+    token_program: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &AccountInfo,
+    expect_writable: bool,
+) -> Result<(), ProgramError> {
+    if token_account.owner.ne(token_program) {
+        msg!("Token account is not owned by the token program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    if expect_writable && !token_account.is_writable {
+        msg!("Token account is not writable");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if token_account
+        .key
+        .ne(&get_associated_token_address_with_program_id(
+            owner,
+            mint,
+            token_program,
+        ))
+    {
+        msg!("Token account is not the associated token account");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Returns the amount held by an SPL Token or Token-2022 account
+pub fn get_token_balance(token_account: &AccountInfo) -> Result<u64, ProgramError> {
+    let token_account_data = token_account.data.borrow();
+    let token_account = StateWithExtensions::<Account>::unpack(&token_account_data)?;
+    Ok(token_account.base.amount)
+}
@@ -0,0 +1,103 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{error::RewardCoreError, reward_config::RewardConfig};
+use jito_reward_sdk::error::RewardError;
+use jito_weight_table_core::weight::Weight;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Updates the voting and slashing configuration of the NCN's reward config. Values that are `None`
+/// are left unchanged and every provided value is bounds checked.
+#[allow(clippy::too_many_arguments)]
+pub fn process_update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    valid_voting_slots: Option<u64>,
+    slots_before_closing_marker_accounts: Option<u64>,
+    consensus_threshold: Option<(u64, u64)>,
+    min_participation: Option<(u64, u64)>,
+    stake_snapshot_slots: Option<u64>,
+    slash_amount: Option<u64>,
+    slashable_epochs: Option<u64>,
+    claim_window_slots: Option<u64>,
+) -> ProgramResult {
+    let [config, ncn, admin] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(admin, false)?;
+    Ncn::load(&restaking_program, ncn, false)?;
+    RewardConfig::load(program_id, ncn.key, config, true)?;
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = RewardConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    if config.admin.ne(admin.key) {
+        msg!("Incorrect reward config admin");
+        return Err(RewardError::IncorrectConfigAdmin.into());
+    }
+
+    if let Some(valid_voting_slots) = valid_voting_slots {
+        msg!("Setting valid voting slots to {}", valid_voting_slots);
+        config.set_valid_voting_slots(valid_voting_slots)?;
+    }
+
+    if let Some(slots_before_closing_marker_accounts) = slots_before_closing_marker_accounts {
+        msg!(
+            "Setting slots before closing marker accounts to {}",
+            slots_before_closing_marker_accounts
+        );
+        config.set_slots_before_closing_marker_accounts(slots_before_closing_marker_accounts)?;
+    }
+
+    if let Some((numerator, denominator)) = consensus_threshold {
+        msg!(
+            "Setting consensus threshold to {}/{}",
+            numerator,
+            denominator
+        );
+        let consensus_threshold = Weight::new(numerator, denominator)
+            .map_err(|_| RewardCoreError::InvalidConsensusThreshold)?;
+        config.set_consensus_threshold(consensus_threshold)?;
+    }
+
+    if let Some((numerator, denominator)) = min_participation {
+        msg!(
+            "Setting minimum participation to {}/{}",
+            numerator,
+            denominator
+        );
+        let min_participation = Weight::new(numerator, denominator)
+            .map_err(|_| RewardCoreError::InvalidMinParticipation)?;
+        config.set_min_participation(min_participation)?;
+    }
+
+    if let Some(stake_snapshot_slots) = stake_snapshot_slots {
+        msg!("Setting stake snapshot slots to {}", stake_snapshot_slots);
+        config.set_stake_snapshot_slots(stake_snapshot_slots)?;
+    }
+
+    if let Some(slash_amount) = slash_amount {
+        msg!("Setting slash amount to {}", slash_amount);
+        config.set_slash_amount(slash_amount)?;
+    }
+
+    if let Some(slashable_epochs) = slashable_epochs {
+        msg!("Setting slashable epochs to {}", slashable_epochs);
+        config.set_slashable_epochs(slashable_epochs)?;
+    }
+
+    if let Some(claim_window_slots) = claim_window_slots {
+        msg!("Setting claim window slots to {}", claim_window_slots);
+        config.set_claim_window_slots(claim_window_slots)?;
+    }
+
+    Ok(())
+}
@@ -1,13 +1,137 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::load_signer;
-use jito_vault_core::{config::Config, vault::Vault};
+use jito_jsm_core::loader::{load_signer, load_system_program};
+use jito_restaking_program::ID as RESTAKING_PROGRAM_ID;
+use jito_reward_core::{
+    epoch_reward_merkle_root::{EpochRewardMerkleRoot, EpochRewardState},
+    epoch_reward_merkle_root_ticket::EpochRewardMerkleRootTicket,
+    reward_config::RewardConfig,
+};
+use jito_reward_sdk::error::RewardError;
+use jito_vault_program::ID as VAULT_PROGRAM_ID;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+use crate::{
+    restaking_helpers::check_ncn_vault_operator_delegation_active,
+    ticket_helpers::{create_ticket_vault_marker, get_vault_operator_stake},
 };
 
+/// Adds the operator's delegated stake from one vault to their ticket.
+/// Called once per vault, a marker account prevents a vault from being counted twice.
+/// Only allowed during the epoch's stake snapshot window, like creating the ticket.
 pub fn process_update_ticket_stake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    // Add code here
+    let [reward_config, vault_config_info, vault_info, ncn_info, operator_info, ncn_operator_state_info, ncn_vault_ticket_info, operator_vault_ticket_info, vault_ncn_ticket_info, vault_operator_delegation_info, epoch_reward_merkle_root, epoch_reward_merkle_root_ticket, epoch_reward_ticket_vault_marker, payer, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let restaking_program = RESTAKING_PROGRAM_ID;
+    let vault_program = VAULT_PROGRAM_ID;
+
+    // Account Checks
+    load_signer(payer, true)?;
+    load_system_program(system_program)?;
+
+    let current_slot = Clock::get()?.slot;
+
+    check_ncn_vault_operator_delegation_active(
+        &restaking_program,
+        &vault_program,
+        vault_config_info,
+        vault_info,
+        ncn_info,
+        operator_info,
+        ncn_operator_state_info,
+        ncn_vault_ticket_info,
+        operator_vault_ticket_info,
+        vault_ncn_ticket_info,
+        vault_operator_delegation_info,
+        current_slot,
+    )?;
+
+    RewardConfig::load(program_id, ncn_info.key, reward_config, false)?;
+    EpochRewardMerkleRoot::load(program_id, ncn_info.key, epoch_reward_merkle_root, true)?;
+
+    let ncn_epoch = {
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+
+        let ncn_epoch = u64::from(epoch_reward_merkle_root.ncn_epoch);
+
+        // The registered stake is the consensus denominator, so it is frozen before voting
+        if epoch_reward_merkle_root.state()? != EpochRewardState::AcceptingDeposits {
+            msg!("Epoch {} is no longer accepting deposits", ncn_epoch);
+            return Err(RewardError::EpochNotAcceptingDeposits.into());
+        }
+
+        if !epoch_reward_merkle_root.is_stake_snapshot_open(current_slot) {
+            msg!("Stake of epoch {} can no longer change", ncn_epoch);
+            return Err(RewardError::StakeSnapshotClosed.into());
+        }
+
+        ncn_epoch
+    };
+
+    EpochRewardMerkleRootTicket::load(
+        program_id,
+        ncn_info.key,
+        operator_info.key,
+        ncn_epoch,
+        epoch_reward_merkle_root_ticket,
+        true,
+    )?;
+
+    {
+        let epoch_reward_merkle_root_ticket_data = epoch_reward_merkle_root_ticket.data.borrow();
+        let epoch_reward_merkle_root_ticket =
+            EpochRewardMerkleRootTicket::try_from_slice_unchecked(
+                &epoch_reward_merkle_root_ticket_data,
+            )?;
+
+        // The stake counted towards a root has to match the ticket, so it is frozen once voted
+        if epoch_reward_merkle_root_ticket.has_voted() {
+            msg!("Ticket stake cannot change after voting");
+            return Err(RewardError::TicketAlreadyVoted.into());
+        }
+    }
+
+    create_ticket_vault_marker(
+        program_id,
+        epoch_reward_merkle_root_ticket.key,
+        vault_info.key,
+        epoch_reward_ticket_vault_marker,
+        payer,
+        system_program,
+    )?;
+
+    let (supported_mint, staked_amount) =
+        get_vault_operator_stake(vault_info, vault_operator_delegation_info)?;
+
+    let mut epoch_reward_merkle_root_ticket_data =
+        epoch_reward_merkle_root_ticket.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root_ticket =
+        EpochRewardMerkleRootTicket::try_from_slice_unchecked_mut(
+            &mut epoch_reward_merkle_root_ticket_data,
+        )?;
+
+    msg!(
+        "Adding {} of mint {} from vault {} to ticket",
+        staked_amount,
+        supported_mint,
+        vault_info.key
+    );
+    epoch_reward_merkle_root_ticket
+        .stake_table
+        .add_value(&supported_mint, staked_amount)?;
+
+    let mut epoch_reward_merkle_root_data = epoch_reward_merkle_root.try_borrow_mut_data()?;
+    let epoch_reward_merkle_root =
+        EpochRewardMerkleRoot::try_from_slice_unchecked_mut(&mut epoch_reward_merkle_root_data)?;
+    epoch_reward_merkle_root.register_stake(&supported_mint, staked_amount)?;
 
-    todo!();
+    Ok(())
 }
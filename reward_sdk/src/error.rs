@@ -5,6 +5,74 @@ use thiserror::Error;
 pub enum RewardError {
     #[error("VaultSlashUnderflow")]
     VaultSlashUnderflow = 1000,
+    #[error("VotingIsOver")]
+    VotingIsOver,
+    #[error("InvalidMerkleRoot")]
+    InvalidMerkleRoot,
+    #[error("IncorrectOperatorAdmin")]
+    IncorrectOperatorAdmin,
+    #[error("TicketAlreadyVoted")]
+    TicketAlreadyVoted,
+    #[error("VaultAlreadyCounted")]
+    VaultAlreadyCounted,
+    #[error("EpochIsNotCurrent")]
+    EpochIsNotCurrent,
+    #[error("InvalidDepositAmount")]
+    InvalidDepositAmount,
+    #[error("NativeMintDeposit")]
+    NativeMintDeposit,
+    #[error("VotingNotDone")]
+    VotingNotDone,
+    #[error("WeightTableNotFinalized")]
+    WeightTableNotFinalized,
+    #[error("ConsensusNotReached")]
+    ConsensusNotReached,
+    #[error("InvalidMerkleProof")]
+    InvalidMerkleProof,
+    #[error("RolledOver")]
+    RolledOver,
+    #[error("ConsensusReached")]
+    ConsensusReached,
+    #[error("MissingRolloverMint")]
+    MissingRolloverMint,
+    #[error("OperatorNotSlashable")]
+    OperatorNotSlashable,
+    #[error("MarkerAccountsNotClosable")]
+    MarkerAccountsNotClosable,
+    #[error("EpochNotAcceptingDeposits")]
+    EpochNotAcceptingDeposits,
+    #[error("VotingNotStarted")]
+    VotingNotStarted,
+    #[error("EpochNotVoting")]
+    EpochNotVoting,
+    #[error("EpochNotDistributing")]
+    EpochNotDistributing,
+    #[error("EpochNotFinalized")]
+    EpochNotFinalized,
+    #[error("DistributionNotFinished")]
+    DistributionNotFinished,
+    #[error("IncorrectConfigAdmin")]
+    IncorrectConfigAdmin,
+    #[error("IncorrectPendingAdmin")]
+    IncorrectPendingAdmin,
+    #[error("IncorrectPauseAuthority")]
+    IncorrectPauseAuthority,
+    #[error("ProgramPaused")]
+    ProgramPaused,
+    #[error("OperatorAlreadySlashed")]
+    OperatorAlreadySlashed,
+    #[error("ClaimWindowOpen")]
+    ClaimWindowOpen,
+    #[error("StakeSnapshotClosed")]
+    StakeSnapshotClosed,
+    #[error("UnsupportedRewardMint")]
+    UnsupportedRewardMint,
+    #[error("UntransferableRewardMint")]
+    UntransferableRewardMint,
+    #[error("SlashWindowClosed")]
+    SlashWindowClosed,
+    #[error("IncorrectFeeAuthority")]
+    IncorrectFeeAuthority,
 }
 
 impl<T> DecodeError<T> for RewardError {
@@ -2,6 +2,15 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use jito_reward_core::merkle_root::MerkleRoot;
 use shank::ShankInstruction;
 
+/// Optional roles on the RewardConfig that the admin can assign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum RewardAdminRole {
+    /// Can pause and resume deposits, distribution, rollovers and early finalization
+    PauseAuthority,
+    /// Sets the reward fee and the wallet it is paid to
+    FeeAuthority,
+}
+
 #[rustfmt::skip]
 #[derive(Debug, BorshSerialize, BorshDeserialize, ShankInstruction)]
 pub enum RewardInstruction {
@@ -17,6 +26,7 @@ pub enum RewardInstruction {
     InitializeConfig{
         valid_voting_slots: u64,
         slots_before_closing_marker_accounts: u64,
+        slash_amount: u64,
     },
 
     /// initializes a reward merkle root for the given epoch
@@ -29,46 +39,248 @@ pub enum RewardInstruction {
     #[account(5, name = "system_program")]
     InitializeEpochRewardMerkleRoot,
 
-    /// initializes a reward merkle root ticket for the given epoch
-    #[account(0, writable, signer, name = "admin")]
+    /// initializes a reward merkle root ticket for the given epoch, seeded with the
+    /// operator's delegated stake from the vault. Only allowed during the epoch's
+    /// stake snapshot window
+    #[account(0, name = "vault_config")]
+    #[account(1, name = "vault")]
+    #[account(2, name = "ncn")]
+    #[account(3, name = "operator")]
+    #[account(4, name = "ncn_operator_state")]
+    #[account(5, name = "ncn_vault_ticket")]
+    #[account(6, name = "operator_vault_ticket")]
+    #[account(7, name = "vault_ncn_ticket")]
+    #[account(8, name = "vault_operator_delegation")]
+    #[account(9, writable, name = "epoch_reward_merkle_root")]
+    #[account(10, writable, name = "epoch_reward_merkle_root_ticket")]
+    #[account(11, writable, name = "epoch_reward_ticket_vault_marker")]
+    #[account(12, writable, signer, name = "admin")]
+    #[account(13, name = "system_program")]
     InitializeEpochRewardMerkleRootTicket,
 
-    /// initializes a reward merkle root ticket for the given epoch
-    #[account(0, writable, signer, name = "admin")]
+    /// Adds the operator's delegated stake from one more vault to their ticket, only during the
+    /// epoch's stake snapshot window
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "vault_config")]
+    #[account(2, name = "vault")]
+    #[account(3, name = "ncn")]
+    #[account(4, name = "operator")]
+    #[account(5, name = "ncn_operator_state")]
+    #[account(6, name = "ncn_vault_ticket")]
+    #[account(7, name = "operator_vault_ticket")]
+    #[account(8, name = "vault_ncn_ticket")]
+    #[account(9, name = "vault_operator_delegation")]
+    #[account(10, writable, name = "epoch_reward_merkle_root")]
+    #[account(11, writable, name = "epoch_reward_merkle_root_ticket")]
+    #[account(12, writable, name = "epoch_reward_ticket_vault_marker")]
+    #[account(13, writable, signer, name = "payer")]
+    #[account(14, name = "system_program")]
     UpdateTicketStake,
 
-    /// initializes a reward merkle root ticket for the given epoch
-    #[account(0, writable, signer, name = "admin")]
+    /// Casts the operator's vote for a merkle root, or moves an existing vote to a new root
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "operator")]
+    #[account(3, writable, name = "epoch_reward_merkle_root")]
+    #[account(4, writable, name = "epoch_reward_merkle_root_ticket")]
+    #[account(5, signer, name = "operator_admin")]
     SubmitTicket {
         root: MerkleRoot,
     },
 
-    /// Deposits rewards to EpochRewardMerkleRoot
-    #[account(0, writable, name = "epoch_reward_merkle_root")]
-    #[account(1, writable, signer, name = "depositor")]
-    DepositRewards,
+    /// Deposits SPL Token or Token-2022 rewards to the EpochRewardMerkleRoot of the current epoch,
+    /// the mint must be in the NCN's weight table of this or the previous epoch
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "reward_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, writable, name = "epoch_reward_merkle_root")]
+    #[account(4, name = "mint")]
+    #[account(5, writable, name = "depositor_token_account")]
+    #[account(6, writable, name = "epoch_reward_merkle_root_token_account")]
+    #[account(7, signer, name = "depositor")]
+    #[account(8, name = "token_program")]
+    #[account(9, name = "weight_table")]
+    DepositRewards {
+        amount: u64,
+    },
 
-    /// Creates a slash ticket if slashing conditions are met
-    #[account(0, writable, name = "slash_ticket")]
-    #[account(1, signer, name = "slasher")]
+    /// Slashes an operator's vault delegation if slashing conditions are met, at most once per epoch
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "vault_config")]
+    #[account(2, writable, name = "vault")]
+    #[account(3, name = "ncn")]
+    #[account(4, name = "operator")]
+    #[account(5, name = "ncn_operator_state")]
+    #[account(6, name = "ncn_vault_ticket")]
+    #[account(7, name = "operator_vault_ticket")]
+    #[account(8, name = "vault_ncn_ticket")]
+    #[account(9, writable, name = "vault_operator_delegation")]
+    #[account(10, name = "ncn_vault_slasher_ticket")]
+    #[account(11, name = "vault_ncn_slasher_ticket")]
+    #[account(12, writable, name = "vault_ncn_slasher_operator_ticket")]
+    #[account(13, writable, name = "vault_token_account")]
+    #[account(14, writable, name = "slasher_token_account")]
+    #[account(15, name = "epoch_reward_merkle_root")]
+    #[account(16, name = "epoch_reward_merkle_root_ticket")]
+    #[account(17, signer, name = "slasher")]
+    #[account(18, name = "vault_program")]
+    #[account(19, name = "token_program")]
+    #[account(20, writable, name = "epoch_reward_slash_marker")]
+    #[account(21, writable, signer, name = "payer")]
+    #[account(22, name = "system_program")]
     Slash,
 
-    /// Transfers rewards from RewardDropbox to the latest EpochRewardMerkleRoot
-    #[account(0, writable, name = "reward_dropbox")]
-    #[account(1, writable, name = "latest_epoch_reward_merkle_root")]
+    /// Transfers rewards from RewardDropbox to the latest EpochRewardMerkleRoot, remaining accounts are
+    /// (mint, reward_dropbox_token_account, epoch_reward_merkle_root_token_account, token_program) groups.
+    /// Wrapped SOL is unwrapped into the dropbox and swept as lamports
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "reward_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, writable, name = "reward_dropbox")]
+    #[account(4, writable, name = "latest_epoch_reward_merkle_root")]
+    #[account(5, name = "weight_table")]
     DropboxToLatest,
 
-    /// Transfers rewards from a delinquent epoch to the current rewards
-    #[account(0, writable, name = "delinquent_epoch_reward_merkle_root")]
-    #[account(1, writable, name = "current_epoch_reward_merkle_root")]
+    /// Transfers rewards from a delinquent epoch to the current rewards, remaining accounts are
+    /// (mint, delinquent_token_account, current_token_account, token_program) groups
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "reward_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, writable, name = "delinquent_epoch_reward_merkle_root")]
+    #[account(4, writable, name = "current_epoch_reward_merkle_root")]
     DelinquentToLatest,
 
-    /// Distributes rewards from a valid EpochRewardMerkleRoot
-    #[account(0, writable, name = "epoch_reward_merkle_root")]
-    #[account(1, writable, name = "distribution_marker")]
-    DistributeCrank,
+    /// Distributes SPL Token or Token-2022 rewards from a valid EpochRewardMerkleRoot
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, writable, name = "epoch_reward_merkle_root")]
+    #[account(3, writable, name = "epoch_reward_distribution_marker")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "recipient")]
+    #[account(6, writable, name = "epoch_reward_merkle_root_token_account")]
+    #[account(7, writable, name = "recipient_token_account")]
+    #[account(8, writable, signer, name = "payer")]
+    #[account(9, name = "token_program")]
+    #[account(10, name = "system_program")]
+    DistributeCrank {
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    },
 
-    /// Closes marker accounts older than a specified number of slots
-    #[account(0, writable, name = "marker_account")]
+    /// Closes distribution marker accounts once the retention window after distribution has passed,
+    /// remaining accounts are (epoch_reward_distribution_marker, payer) pairs
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "epoch_reward_merkle_root")]
     CloseMarkerAccounts,
+
+    /// Deposits native SOL rewards to the EpochRewardMerkleRoot of the current epoch
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "reward_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, writable, name = "epoch_reward_merkle_root")]
+    #[account(4, writable, signer, name = "depositor")]
+    #[account(5, name = "system_program")]
+    DepositSolRewards {
+        amount: u64,
+    },
+
+    /// Distributes native SOL rewards from a valid EpochRewardMerkleRoot
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, writable, name = "epoch_reward_merkle_root")]
+    #[account(3, writable, name = "epoch_reward_distribution_marker")]
+    #[account(4, writable, name = "recipient")]
+    #[account(5, writable, signer, name = "payer")]
+    #[account(6, name = "system_program")]
+    DistributeSolCrank {
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    },
+
+    /// Initializes the NCN's reward dropbox, remaining accounts are
+    /// (mint, reward_dropbox_token_account, token_program) triplets to create ATAs for
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, writable, name = "reward_dropbox")]
+    #[account(3, writable, signer, name = "payer")]
+    #[account(4, name = "associated_token_program")]
+    #[account(5, name = "system_program")]
+    InitializeRewardDropbox,
+
+    /// Records the outcome of voting once the winning root reaches consensus or the voting
+    /// window ends. Permissionless
+    #[account(0, name = "reward_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "weight_table")]
+    #[account(3, writable, name = "epoch_reward_merkle_root")]
+    FinalizeConsensus,
+
+    /// Updates the voting and slashing configuration, only the provided values are changed.
+    /// Weights are passed as (numerator, denominator). Epochs keep the voting values they were
+    /// initialized with, so those changes apply from the next initialized epoch
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "admin")]
+    UpdateConfig {
+        valid_voting_slots: Option<u64>,
+        slots_before_closing_marker_accounts: Option<u64>,
+        consensus_threshold: Option<(u64, u64)>,
+        min_participation: Option<(u64, u64)>,
+        stake_snapshot_slots: Option<u64>,
+        slash_amount: Option<u64>,
+        slashable_epochs: Option<u64>,
+        claim_window_slots: Option<u64>,
+    },
+
+    /// Starts transferring the admin role, the new admin has to accept it with AcceptAdmin
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "admin")]
+    #[account(3, name = "new_admin")]
+    SetNewAdmin,
+
+    /// Completes the admin transfer started by SetNewAdmin
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "new_admin")]
+    AcceptAdmin,
+
+    /// Assigns an optional role, passing the system program as the new admin unsets it
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "admin")]
+    #[account(3, name = "new_admin")]
+    SetSecondaryAdmin {
+        role: RewardAdminRole,
+    },
+
+    /// Pauses or resumes deposits, dropbox sweeps, distribution, rollovers and finalizing before
+    /// the voting window ends. Voting, admin instructions and slashing are never paused
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "pause_authority")]
+    SetPaused {
+        paused: bool,
+    },
+
+    /// Ends the distribution of an epoch with consensus once nothing is left to pay out or the
+    /// claim window is over, moving what is left to the current rewards. Remaining accounts are
+    /// (mint, epoch_token_account, current_token_account, token_program) groups
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "reward_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, writable, name = "epoch_reward_merkle_root")]
+    #[account(4, writable, name = "current_epoch_reward_merkle_root")]
+    SweepToLatest,
+
+    /// Sets the fee operators bake into the merkle root of later epochs, `fee_bps` of the
+    /// rewards paid to `fee_wallet`. Only the fee authority can call it
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "fee_authority")]
+    #[account(3, name = "fee_wallet")]
+    SetFees {
+        fee_bps: u64,
+    },
 }
@@ -1 +1,3 @@
 pub const WEIGHT_TABLE_DISCRIMINATOR: u8 = 0x01;
+pub const WEIGHT_TABLE_CONFIG_DISCRIMINATOR: u8 = 0x02;
+pub const WEIGHT_TABLE_HISTORY_DISCRIMINATOR: u8 = 0x03;
@@ -5,6 +5,8 @@ use thiserror::Error;
 pub enum WeightTableError {
     #[error("No more table slots available")]
     NoMoreTableSlots = 0x2000,
+    #[error("Weight not found")]
+    WeightNotFound = 0x2001,
     #[error("Zero in the denominator")]
     DenominatorIsZero = 0x2100,
     #[error("Overflow")]
@@ -16,6 +18,43 @@ pub enum WeightTableError {
     IncorrectWeightTableAdmin = 0x2200,
     #[error("Cannnot create future weight tables")]
     CannotCreateFutureWeightTables = 0x2201,
+    #[error("Incorrect NCN admin")]
+    IncorrectNcnAdmin = 0x2202,
+    #[error("Weight table is finalized")]
+    WeightTableFinalized = 0x2203,
+    #[error("Weight table is not finalized")]
+    WeightTableNotFinalized = 0x2204,
+    #[error("Previous weight table must be from an earlier epoch")]
+    InvalidPreviousWeightTable = 0x2205,
+    #[error("Weight table is already used by reward voting")]
+    WeightTableInUse = 0x2206,
+    #[error("Vault count does not match the NCN")]
+    VaultCountMismatch = 0x2207,
+    #[error("Duplicate vault")]
+    DuplicateVault = 0x2208,
+    #[error("Vault supported mint has no weight")]
+    MissingVaultMintWeight = 0x2209,
+    #[error("Epochs before close must be greater than zero")]
+    InvalidEpochsBeforeClose = 0x220A,
+    #[error("Weight table is too recent to close")]
+    WeightTableNotClosable = 0x220B,
+    #[error("Weight table is referenced by a reward epoch that is still voting")]
+    WeightTableReferenced = 0x220C,
+
+    #[error("Oracle price is stale")]
+    StaleOraclePrice = 0x2300,
+    #[error("Oracle confidence interval is too wide")]
+    OracleConfidenceTooWide = 0x2301,
+    #[error("Invalid oracle price")]
+    InvalidOraclePrice = 0x2302,
+    #[error("No price feed for mint")]
+    PriceFeedNotFound = 0x2303,
+    #[error("Incorrect price feed")]
+    IncorrectPriceFeed = 0x2304,
+    #[error("Invalid max stale slots")]
+    InvalidMaxStaleSlots = 0x2305,
+    #[error("Invalid max confidence bps")]
+    InvalidMaxConfidenceBps = 0x2306,
 }
 
 impl<T> DecodeError<T> for WeightTableError {
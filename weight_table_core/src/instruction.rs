@@ -8,31 +8,173 @@ pub enum WeightTableInstruction {
     /// Initializes global configuration
     #[account(0, name = "restaking_config")]
     #[account(1, name = "ncn")]
-    #[account(2, writable, signer, name = "weight_table")]
-    #[account(3, writable, signer, name = "weight_table_admin")]
-    #[account(4, name = "restaking_program_id")]
-    #[account(5, name = "system_program")]
+    #[account(2, name = "weight_table_config")]
+    #[account(3, writable, signer, name = "weight_table")]
+    #[account(4, writable, signer, name = "weight_table_admin")]
+    #[account(5, name = "restaking_program_id")]
+    #[account(6, name = "system_program")]
     InitializeWeightTable{
         first_slot_of_ncn_epoch: Option<u64>,
     },
 
     /// Updates the weight table
     #[account(0, name = "ncn")]
-    #[account(1, writable, name = "weight_table")]
-    #[account(2, signer, name = "weight_table_admin")]
-    #[account(3, name = "restaking_program_id")]
+    #[account(1, name = "weight_table_config")]
+    #[account(2, writable, name = "weight_table")]
+    #[account(3, signer, name = "weight_table_admin")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "restaking_program_id")]
     UpdateWeightTable{
         ncn_epoch: u64,
         weight_numerator: u64,
         weight_denominator: u64,
     },
 
+    /// Finalizes the weight table once every active vault's supported mint has a weight and
+    /// records it in the history if the history is initialized, the NCN's
+    /// (ncn_vault_ticket, vault) pairs are passed as remaining accounts
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "weight_table_config")]
+    #[account(3, writable, name = "weight_table")]
+    #[account(4, writable, name = "weight_table_history")]
+    #[account(5, signer, name = "weight_table_admin")]
+    #[account(6, name = "restaking_program_id")]
+    FinalizeWeightTable{
+        ncn_epoch: u64,
+    },
+
+    /// Initializes the NCN's weight table config, signed by the NCN admin
+    #[account(0, name = "ncn")]
+    #[account(1, writable, name = "weight_table_config")]
+    #[account(2, writable, signer, name = "ncn_admin")]
+    #[account(3, name = "weight_table_admin")]
+    #[account(4, name = "restaking_program_id")]
+    #[account(5, name = "system_program")]
+    InitializeWeightTableConfig,
+
+    /// Sets or rotates the weight table admin, signed by the NCN admin
     #[account(0, name = "ncn")]
-    #[account(1, writable, name = "weight_table")]
+    #[account(1, writable, name = "weight_table_config")]
+    #[account(2, signer, name = "ncn_admin")]
+    #[account(3, name = "new_weight_table_admin")]
+    #[account(4, name = "restaking_program_id")]
+    SetWeightTableAdmin,
+
+    /// Sets the Switchboard price feed used to weight an SPL Token or Token-2022 mint, with
+    /// bounded staleness and confidence limits
+    #[account(0, name = "ncn")]
+    #[account(1, writable, name = "weight_table_config")]
     #[account(2, signer, name = "weight_table_admin")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "price_feed")]
+    #[account(5, name = "restaking_program_id")]
+    SetPriceFeed{
+        max_stale_slots: u64,
+        max_confidence_bps: u64,
+    },
+
+    /// Updates a mint's weight from its configured price feed, permissionless
+    #[account(0, name = "ncn")]
+    #[account(1, name = "weight_table_config")]
+    #[account(2, writable, name = "weight_table")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "price_feed")]
+    #[account(5, name = "restaking_program_id")]
+    UpdateWeightFromOracle{
+        ncn_epoch: u64,
+    },
+
+    /// Updates the weights of many mints, the mints are passed as remaining accounts
+    /// in the same order as `weights`
+    #[account(0, name = "ncn")]
+    #[account(1, name = "weight_table_config")]
+    #[account(2, writable, name = "weight_table")]
+    #[account(3, signer, name = "weight_table_admin")]
+    #[account(4, name = "restaking_program_id")]
+    BatchUpdateWeightTable{
+        ncn_epoch: u64,
+        weights: Vec<(u64, u64)>,
+    },
+
+    /// Initializes a weight table with the entries of an earlier, finalized weight table
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "weight_table_config")]
+    #[account(3, name = "previous_weight_table")]
+    #[account(4, writable, name = "weight_table")]
+    #[account(5, writable, signer, name = "weight_table_admin")]
+    #[account(6, name = "restaking_program_id")]
+    #[account(7, name = "system_program")]
+    InitializeWeightTableFromPrevious{
+        previous_ncn_epoch: u64,
+        first_slot_of_ncn_epoch: Option<u64>,
+    },
+
+    /// Reopens a finalized weight table, only allowed before any reward voting used it.
+    /// Flags the epoch's snapshot in the weight table history as reopened, if the history is
+    /// initialized
+    #[account(0, name = "ncn")]
+    #[account(1, name = "weight_table_config")]
+    #[account(2, writable, name = "weight_table")]
+    #[account(3, name = "epoch_reward_merkle_root")]
+    #[account(4, signer, name = "weight_table_admin")]
+    #[account(5, name = "restaking_program_id")]
+    #[account(6, writable, name = "weight_table_history")]
+    UnfinalizeWeightTable{
+        ncn_epoch: u64,
+    },
+
+    /// Removes a mint from the weight table
+    #[account(0, name = "ncn")]
+    #[account(1, name = "weight_table_config")]
+    #[account(2, writable, name = "weight_table")]
+    #[account(3, signer, name = "weight_table_admin")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "restaking_program_id")]
+    RemoveWeight{
+        ncn_epoch: u64,
+    },
+
+    /// Sets how many NCN epochs must pass before a weight table can be closed,
+    /// signed by the NCN admin
+    #[account(0, name = "ncn")]
+    #[account(1, writable, name = "weight_table_config")]
+    #[account(2, signer, name = "ncn_admin")]
     #[account(3, name = "restaking_program_id")]
-    FinalizeWeightTable{
+    SetEpochsBeforeClose{
+        epochs_before_close: u64,
+    },
+
+    /// Closes an expired weight table and sends its lamports to the receiver
+    #[account(0, name = "restaking_config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "weight_table_config")]
+    #[account(3, writable, name = "weight_table")]
+    #[account(4, name = "epoch_reward_merkle_root")]
+    #[account(5, signer, name = "weight_table_admin")]
+    #[account(6, writable, name = "receiver")]
+    #[account(7, name = "restaking_program_id")]
+    CloseWeightTable{
         ncn_epoch: u64,
     },
 
+    /// Creates the NCN's weight table history at 10KB, permissionless. It is initialized once
+    /// ReallocWeightTableHistory grows it to full size
+    #[account(0, name = "ncn")]
+    #[account(1, writable, name = "weight_table_history")]
+    #[account(2, writable, signer, name = "payer")]
+    #[account(3, name = "restaking_program_id")]
+    #[account(4, name = "system_program")]
+    InitializeWeightTableHistory,
+
+    /// Grows the NCN's weight table history by up to 10KB, it is initialized once it
+    /// reaches its full size. Permissionless
+    #[account(0, name = "ncn")]
+    #[account(1, writable, name = "weight_table_history")]
+    #[account(2, writable, signer, name = "payer")]
+    #[account(3, name = "restaking_program_id")]
+    #[account(4, name = "system_program")]
+    ReallocWeightTableHistory,
+
 }
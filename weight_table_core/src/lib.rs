@@ -1,5 +1,8 @@
 pub mod discriminators;
 pub mod error;
 pub mod instruction;
+pub mod oracle;
 pub mod weight;
 pub mod weight_table;
+pub mod weight_table_config;
+pub mod weight_table_history;
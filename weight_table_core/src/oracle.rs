@@ -0,0 +1,170 @@
+use switchboard_on_demand::PullFeedAccountData;
+
+use crate::{error::WeightTableError, weight::Weight};
+
+/// Oracle weights are stored with 9 decimals
+pub const ORACLE_WEIGHT_DENOMINATOR: u64 = 1_000_000_000;
+
+/// Basis points in 100%
+pub const MAX_BPS: u64 = 10_000;
+
+/// Converts the feed's current result into a weight, the feed is expected to price one whole
+/// token of a mint with `mint_decimals` in SOL. Weights are applied to raw token amounts, so
+/// the price is scaled to lamports per base unit of the mint. Fails if the result is older
+/// than `max_stale_slots` or if its standard deviation is wider than `max_confidence_bps` of
+/// the price.
+pub fn weight_from_price_feed(
+    feed: &PullFeedAccountData,
+    mint_decimals: u8,
+    current_slot: u64,
+    max_stale_slots: u64,
+    max_confidence_bps: u64,
+) -> Result<Weight, WeightTableError> {
+    let result_slot = feed
+        .result
+        .result_slot()
+        .ok_or(WeightTableError::StaleOraclePrice)?;
+
+    if result_slot.saturating_add(max_stale_slots) < current_slot {
+        return Err(WeightTableError::StaleOraclePrice);
+    }
+
+    let price = feed.result.value;
+    let std_dev = feed.result.std_dev;
+
+    if price <= 0 || std_dev < 0 {
+        return Err(WeightTableError::InvalidOraclePrice);
+    }
+
+    let confidence_limit = price
+        .checked_mul(max_confidence_bps as i128)
+        .ok_or(WeightTableError::ArithmeticOverflow)?;
+    let confidence = std_dev
+        .checked_mul(MAX_BPS as i128)
+        .ok_or(WeightTableError::ArithmeticOverflow)?;
+
+    if confidence > confidence_limit {
+        return Err(WeightTableError::OracleConfidenceTooWide);
+    }
+
+    // Feed values have 18 decimals. The weight is price * 10^(9 - mint_decimals) lamports per
+    // base unit with 9 decimals of its own, which leaves dividing the value by 10^mint_decimals
+    let numerator: u64 = 10_i128
+        .checked_pow(mint_decimals as u32)
+        .and_then(|scale| price.checked_div(scale))
+        .and_then(|numerator| u64::try_from(numerator).ok())
+        .ok_or(WeightTableError::ArithmeticOverflow)?;
+
+    if numerator == 0 {
+        return Err(WeightTableError::InvalidOraclePrice);
+    }
+
+    Weight::new(numerator, ORACLE_WEIGHT_DENOMINATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::*;
+
+    /// A feed account as the oracle program would leave it after an update
+    fn price_feed_fixture(price: i128, std_dev: i128, slot: u64) -> PullFeedAccountData {
+        let mut feed = PullFeedAccountData::zeroed();
+        feed.result.value = price;
+        feed.result.std_dev = std_dev;
+        feed.result.slot = slot;
+        feed.max_staleness = 100;
+        feed
+    }
+
+    const ONE: i128 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn test_weight_from_price_feed() {
+        // 1.05 SOL
+        let feed = price_feed_fixture(ONE + ONE / 20, ONE / 1_000, 100);
+
+        let weight = weight_from_price_feed(&feed, 9, 110, 10, 50).unwrap();
+        assert_eq!(
+            weight,
+            Weight::new(1_050_000_000, ORACLE_WEIGHT_DENOMINATOR).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_weight_from_price_feed_mixed_decimals() {
+        // 150 SOL per whole token
+        let feed = price_feed_fixture(150 * ONE, 0, 100);
+
+        let nine_decimals = weight_from_price_feed(&feed, 9, 100, 10, 50).unwrap();
+        let six_decimals = weight_from_price_feed(&feed, 6, 100, 10, 50).unwrap();
+        let twelve_decimals = weight_from_price_feed(&feed, 12, 100, 10, 50).unwrap();
+
+        // One whole token is worth 150 SOL whatever the mint's decimals
+        assert_eq!(nine_decimals.apply(1_000_000_000), Ok(150_000_000_000));
+        assert_eq!(six_decimals.apply(1_000_000), Ok(150_000_000_000));
+        assert_eq!(
+            twelve_decimals.apply(1_000_000_000_000),
+            Ok(150_000_000_000)
+        );
+
+        // So equal value gets equal normalized stake
+        assert_eq!(
+            six_decimals.apply(2_500_000),
+            nine_decimals.apply(2_500_000_000)
+        );
+
+        assert_eq!(
+            weight_from_price_feed(&feed, 39, 100, 10, 50),
+            Err(WeightTableError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_weight_from_price_feed_stale() {
+        let feed = price_feed_fixture(ONE, 0, 100);
+
+        assert!(weight_from_price_feed(&feed, 9, 110, 10, 50).is_ok());
+        assert_eq!(
+            weight_from_price_feed(&feed, 9, 111, 10, 50),
+            Err(WeightTableError::StaleOraclePrice)
+        );
+
+        // A feed that was never updated has no result
+        let feed = price_feed_fixture(ONE, 0, 0);
+        assert_eq!(
+            weight_from_price_feed(&feed, 9, 0, 10, 50),
+            Err(WeightTableError::StaleOraclePrice)
+        );
+    }
+
+    #[test]
+    fn test_weight_from_price_feed_confidence() {
+        // 0.5% standard deviation
+        let feed = price_feed_fixture(ONE, ONE / 200, 100);
+
+        assert!(weight_from_price_feed(&feed, 9, 100, 10, 50).is_ok());
+        assert_eq!(
+            weight_from_price_feed(&feed, 9, 100, 10, 49),
+            Err(WeightTableError::OracleConfidenceTooWide)
+        );
+    }
+
+    #[test]
+    fn test_weight_from_price_feed_invalid_price() {
+        assert_eq!(
+            weight_from_price_feed(&price_feed_fixture(0, 0, 100), 9, 100, 10, 50),
+            Err(WeightTableError::InvalidOraclePrice)
+        );
+        assert_eq!(
+            weight_from_price_feed(&price_feed_fixture(-ONE, 0, 100), 9, 100, 10, 50),
+            Err(WeightTableError::InvalidOraclePrice)
+        );
+        // Below the weight's precision
+        assert_eq!(
+            weight_from_price_feed(&price_feed_fixture(1, 0, 100), 9, 100, 10, 50),
+            Err(WeightTableError::InvalidOraclePrice)
+        );
+    }
+}
@@ -1,5 +1,3 @@
-use std::u64;
-
 use bytemuck::{Pod, Zeroable};
 use jito_bytemuck::types::PodU64;
 use shank::ShankType;
@@ -33,7 +31,7 @@ impl Weight {
         })
     }
 
-    fn is_zero(&self) -> bool {
+    pub fn is_zero(&self) -> bool {
         self.numerator() == 0
     }
 
@@ -102,6 +100,7 @@ impl Weight {
         self.compare_weights(other, |ad, bc| ad <= bc)
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn eq(&self, other: &Self) -> bool {
         self.compare_weights(other, |ad, bc| ad == bc)
     }
@@ -241,6 +240,19 @@ impl Weight {
 
         weight.simplify()
     }
+
+    /// Applies the weight to a token amount, rounding down.
+    /// A u64 amount times a u64 numerator always fits in a u128.
+    pub fn apply(&self, amount: u64) -> Result<u128, WeightTableError> {
+        if self.denominator() == 0 {
+            return Err(WeightTableError::DenominatorIsZero);
+        }
+
+        (amount as u128)
+            .checked_mul(self.numerator() as u128)
+            .and_then(|value| value.checked_div(self.denominator() as u128))
+            .ok_or(WeightTableError::ArithmeticOverflow)
+    }
 }
 
 impl Default for Weight {
@@ -274,6 +286,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_apply() {
+        let weight = Weight::new(3, 2).unwrap();
+        assert_eq!(weight.apply(10).unwrap(), 15);
+
+        // Rounds down
+        assert_eq!(weight.apply(3).unwrap(), 4);
+        assert_eq!(Weight::new(1, 3).unwrap().apply(2).unwrap(), 0);
+
+        assert_eq!(Weight::new(0, 1).unwrap().apply(u64::MAX).unwrap(), 0);
+        assert_eq!(
+            Weight::new(u64::MAX, 1).unwrap().apply(u64::MAX).unwrap(),
+            (u64::MAX as u128) * (u64::MAX as u128)
+        );
+
+        let zeroed: Weight = Zeroable::zeroed();
+        assert!(matches!(
+            zeroed.apply(1),
+            Err(WeightTableError::DenominatorIsZero)
+        ));
+    }
+
     #[test]
     fn test_is_zero() {
         assert!(Weight::new(0, 1).unwrap().is_zero());
@@ -25,8 +25,11 @@ pub struct WeightTable {
     /// Bump seed for the PDA
     pub bump: u8,
 
+    /// Times the weight table was unfinalized
+    reopen_count: PodU64,
+
     /// Reserved space
-    reserved: [u8; 128],
+    reserved: [u8; 120],
 
     /// The weight table
     pub table: [WeightEntry; 32],
@@ -47,7 +50,8 @@ impl WeightTable {
             slot_created: PodU64::from(slot_created),
             slot_finalized: PodU64::from(Self::NOT_FINALIZED),
             bump,
-            reserved: [0; 128],
+            reopen_count: PodU64::from(0),
+            reserved: [0; 120],
             table: [WeightEntry::default(); Self::MAX_TABLE_ENTRIES],
         }
     }
@@ -86,6 +90,28 @@ impl WeightTable {
             .map(|entry| entry.weight)
     }
 
+    /// Sums `stake` after applying each mint's weight, each mint's contribution is rounded
+    /// down. Mints without a weight do not count towards the total.
+    pub fn normalize_stake<'a>(
+        &self,
+        stake: impl IntoIterator<Item = (&'a Pubkey, u64)>,
+    ) -> Result<u128, WeightTableError> {
+        stake.into_iter().try_fold(0_u128, |total, (mint, amount)| {
+            match self.find_weight(mint) {
+                Some(weight) => total
+                    .checked_add(weight.apply(amount)?)
+                    .ok_or(WeightTableError::ArithmeticOverflow),
+                None => Ok(total),
+            }
+        })
+    }
+
+    /// Whether `mint` has a weight that counts its stake, a zero weight ignores it
+    pub fn has_nonzero_weight(&self, mint: &Pubkey) -> bool {
+        self.find_weight(mint)
+            .is_some_and(|weight| !weight.is_zero())
+    }
+
     pub fn set_weight(&mut self, mint: &Pubkey, weight: Weight) -> Result<(), WeightTableError> {
         let entry = self
             .table
@@ -94,7 +120,7 @@ impl WeightTable {
 
         match entry {
             Some(entry) => {
-                entry.weight = weight.into();
+                entry.weight = weight;
 
                 if entry.mint == Pubkey::default() {
                     entry.mint = *mint;
@@ -106,6 +132,26 @@ impl WeightTable {
         Ok(())
     }
 
+    /// Copies every entry of a previous epoch's table into this one
+    pub fn copy_entries_from(&mut self, previous: &Self) {
+        self.table = previous.table;
+    }
+
+    /// Removes the entry for `mint`, shifting the following entries down so the
+    /// filled entries stay contiguous
+    pub fn remove_weight(&mut self, mint: &Pubkey) -> Result<(), WeightTableError> {
+        let index = self
+            .table
+            .iter()
+            .position(|entry| !entry.is_empty() && entry.mint == *mint)
+            .ok_or(WeightTableError::WeightNotFound)?;
+
+        self.table.copy_within(index + 1.., index);
+        self.table[Self::MAX_TABLE_ENTRIES - 1] = WeightEntry::default();
+
+        Ok(())
+    }
+
     pub fn slot_created(&self) -> u64 {
         self.slot_created.into()
     }
@@ -122,6 +168,26 @@ impl WeightTable {
         self.slot_finalized = PodU64::from(current_slot);
     }
 
+    pub fn reopen_count(&self) -> u64 {
+        self.reopen_count.into()
+    }
+
+    /// Reopens a finalized weight table for updates, counting each reopen
+    pub fn unfinalize(&mut self) -> Result<(), WeightTableError> {
+        if !self.finalized() {
+            return Err(WeightTableError::WeightTableNotFinalized);
+        }
+
+        self.reopen_count = PodU64::from(
+            self.reopen_count()
+                .checked_add(1)
+                .ok_or(WeightTableError::ArithmeticOverflow)?,
+        );
+        self.slot_finalized = PodU64::from(Self::NOT_FINALIZED);
+
+        Ok(())
+    }
+
     pub fn load(
         program_id: &Pubkey,
         weight_table: &AccountInfo,
@@ -223,6 +289,51 @@ mod tests {
         assert_eq!(table.find_weight(&Pubkey::new_unique()), None);
     }
 
+    #[test]
+    fn test_weight_table_has_nonzero_weight() {
+        let mut table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        let mint1 = Pubkey::new_unique();
+        let mint2 = Pubkey::new_unique();
+
+        table
+            .set_weight(&mint1, Weight::new(1, 2).unwrap())
+            .unwrap();
+        table
+            .set_weight(&mint2, Weight::new(0, 1).unwrap())
+            .unwrap();
+
+        assert!(table.has_nonzero_weight(&mint1));
+        assert!(!table.has_nonzero_weight(&mint2));
+        assert!(!table.has_nonzero_weight(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_weight_table_normalize_stake() {
+        let mut table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        let mint1 = Pubkey::new_unique();
+        let mint2 = Pubkey::new_unique();
+        let unweighted_mint = Pubkey::new_unique();
+
+        table
+            .set_weight(&mint1, Weight::new(1, 3).unwrap())
+            .unwrap();
+        table
+            .set_weight(&mint2, Weight::new(2, 1).unwrap())
+            .unwrap();
+
+        assert_eq!(table.normalize_stake([]).unwrap(), 0);
+
+        // 10 / 3 rounds down to 3 before summing
+        let stake = [(&mint1, 10), (&mint2, 5), (&unweighted_mint, 1_000)];
+        assert_eq!(table.normalize_stake(stake).unwrap(), 13);
+
+        let stake = [(&mint2, u64::MAX), (&mint2, u64::MAX)];
+        assert_eq!(
+            table.normalize_stake(stake).unwrap(),
+            (u64::MAX as u128) * 4
+        );
+    }
+
     #[test]
     fn test_weight_table_set_weight() {
         let ncn = Pubkey::new_unique();
@@ -248,6 +359,115 @@ mod tests {
         assert!(matches!(result, Err(WeightTableError::NoMoreTableSlots)));
     }
 
+    #[test]
+    fn test_weight_table_copy_entries_from() {
+        let ncn = Pubkey::new_unique();
+
+        let mut previous = WeightTable::new(ncn, 0, 0, 0);
+        let mint1 = Pubkey::new_unique();
+        let mint2 = Pubkey::new_unique();
+        previous
+            .set_weight(&mint1, Weight::new(1, 2).unwrap())
+            .unwrap();
+        previous
+            .set_weight(&mint2, Weight::new(3, 4).unwrap())
+            .unwrap();
+        previous.finalize(10);
+
+        let mut table = WeightTable::new(ncn, 1, 20, 0);
+        table.copy_entries_from(&previous);
+
+        assert_eq!(table.entry_count(), 2);
+        assert_eq!(table.find_weight(&mint1), Some(Weight::new(1, 2).unwrap()));
+        assert_eq!(table.find_weight(&mint2), Some(Weight::new(3, 4).unwrap()));
+        assert_eq!(table.ncn_epoch, PodU64::from(1));
+        assert_eq!(table.slot_created(), 20);
+        assert!(!table.finalized());
+
+        // Edits to the new table leave the previous one untouched
+        table
+            .set_weight(&mint1, Weight::new(5, 6).unwrap())
+            .unwrap();
+        assert_eq!(
+            previous.find_weight(&mint1),
+            Some(Weight::new(1, 2).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_weight_table_remove_weight() {
+        let mut table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        let mint1 = Pubkey::new_unique();
+        let mint2 = Pubkey::new_unique();
+        let mint3 = Pubkey::new_unique();
+
+        table
+            .set_weight(&mint1, Weight::new(1, 2).unwrap())
+            .unwrap();
+        table
+            .set_weight(&mint2, Weight::new(3, 4).unwrap())
+            .unwrap();
+        table
+            .set_weight(&mint3, Weight::new(5, 6).unwrap())
+            .unwrap();
+
+        table.remove_weight(&mint2).unwrap();
+        assert_eq!(table.entry_count(), 2);
+        assert_eq!(table.find_weight(&mint2), None);
+
+        // Entries are compacted
+        assert_eq!(table.table[0].mint, mint1);
+        assert_eq!(table.table[1].mint, mint3);
+        assert!(table.table[2].is_empty());
+
+        let result = table.remove_weight(&mint2);
+        assert!(matches!(result, Err(WeightTableError::WeightNotFound)));
+        let result = table.remove_weight(&Pubkey::default());
+        assert!(matches!(result, Err(WeightTableError::WeightNotFound)));
+    }
+
+    #[test]
+    fn test_weight_table_remove_weight_at_capacity() {
+        let mut table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        let mints: Vec<Pubkey> = (0..WeightTable::MAX_TABLE_ENTRIES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+
+        for mint in mints.iter() {
+            table.set_weight(mint, Weight::new(1, 1).unwrap()).unwrap();
+        }
+        assert_eq!(table.entry_count(), WeightTable::MAX_TABLE_ENTRIES);
+
+        let new_mint = Pubkey::new_unique();
+        let result = table.set_weight(&new_mint, Weight::new(1, 1).unwrap());
+        assert!(matches!(result, Err(WeightTableError::NoMoreTableSlots)));
+
+        // Removing from the middle frees the last slot
+        table.remove_weight(&mints[10]).unwrap();
+        assert_eq!(table.entry_count(), WeightTable::MAX_TABLE_ENTRIES - 1);
+        assert!(table.table[WeightTable::MAX_TABLE_ENTRIES - 1].is_empty());
+
+        table
+            .set_weight(&new_mint, Weight::new(2, 3).unwrap())
+            .unwrap();
+        assert_eq!(table.entry_count(), WeightTable::MAX_TABLE_ENTRIES);
+        assert_eq!(
+            table.find_weight(&new_mint),
+            Some(Weight::new(2, 3).unwrap())
+        );
+        assert_eq!(table.find_weight(&mints[10]), None);
+        for mint in mints.iter().filter(|mint| **mint != mints[10]) {
+            assert_eq!(table.find_weight(mint), Some(Weight::new(1, 1).unwrap()));
+        }
+
+        // Removing the last entry also frees its slot
+        table.remove_weight(&new_mint).unwrap();
+        table
+            .set_weight(&mints[10], Weight::new(1, 1).unwrap())
+            .unwrap();
+        assert_eq!(table.entry_count(), WeightTable::MAX_TABLE_ENTRIES);
+    }
+
     #[test]
     fn test_weight_entry_new() {
         let mint = Pubkey::new_unique();
@@ -278,4 +498,26 @@ mod tests {
         weight_table.finalize(0);
         assert!(weight_table.finalized());
     }
+
+    #[test]
+    fn test_weight_table_unfinalize() {
+        let mut weight_table = WeightTable::new(Pubkey::new_unique(), 0, 0, 0);
+        assert_eq!(weight_table.reopen_count(), 0);
+
+        let result = weight_table.unfinalize();
+        assert!(matches!(
+            result,
+            Err(WeightTableError::WeightTableNotFinalized)
+        ));
+
+        weight_table.finalize(10);
+        weight_table.unfinalize().unwrap();
+        assert!(!weight_table.finalized());
+        assert_eq!(weight_table.slot_finalized(), WeightTable::NOT_FINALIZED);
+        assert_eq!(weight_table.reopen_count(), 1);
+
+        weight_table.finalize(20);
+        weight_table.unfinalize().unwrap();
+        assert_eq!(weight_table.reopen_count(), 2);
+    }
 }
@@ -0,0 +1,324 @@
+use bytemuck::{Pod, Zeroable};
+use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use shank::{ShankAccount, ShankType};
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{discriminators::WEIGHT_TABLE_CONFIG_DISCRIMINATOR, error::WeightTableError};
+
+// PDA'd ["WEIGHT_TABLE_CONFIG", NCN]
+#[derive(Debug, Clone, Copy, Zeroable, ShankType, Pod, AccountDeserialize, ShankAccount)]
+#[repr(C)]
+pub struct WeightTableConfig {
+    /// The NCN the config belongs to
+    pub ncn: Pubkey,
+
+    /// Creates, updates and finalizes the NCN's weight tables, set by the NCN admin
+    pub weight_table_admin: Pubkey,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+
+    /// How many NCN epochs must pass before a weight table can be closed
+    epochs_before_close: PodU64,
+
+    /// Switchboard price feeds used to update weights permissionlessly
+    pub price_feeds: [PriceFeedEntry; 32],
+
+    /// Reserved space
+    reserved: [u8; 119],
+}
+
+impl Discriminator for WeightTableConfig {
+    const DISCRIMINATOR: u8 = WEIGHT_TABLE_CONFIG_DISCRIMINATOR;
+}
+
+impl WeightTableConfig {
+    pub const MAX_PRICE_FEEDS: usize = 32;
+    pub const DEFAULT_EPOCHS_BEFORE_CLOSE: u64 = 10;
+
+    pub fn new(ncn: Pubkey, weight_table_admin: Pubkey, bump: u8) -> Self {
+        Self {
+            ncn,
+            weight_table_admin,
+            bump,
+            epochs_before_close: PodU64::from(Self::DEFAULT_EPOCHS_BEFORE_CLOSE),
+            price_feeds: [PriceFeedEntry::default(); Self::MAX_PRICE_FEEDS],
+            reserved: [0; 119],
+        }
+    }
+
+    pub fn epochs_before_close(&self) -> u64 {
+        self.epochs_before_close.into()
+    }
+
+    pub fn set_epochs_before_close(
+        &mut self,
+        epochs_before_close: u64,
+    ) -> Result<(), WeightTableError> {
+        if epochs_before_close == 0 {
+            return Err(WeightTableError::InvalidEpochsBeforeClose);
+        }
+        self.epochs_before_close = PodU64::from(epochs_before_close);
+        Ok(())
+    }
+
+    /// Whether the weight table of `ncn_epoch` is old enough to be closed at `current_ncn_epoch`
+    pub fn can_close_weight_table(&self, ncn_epoch: u64, current_ncn_epoch: u64) -> bool {
+        ncn_epoch
+            .checked_add(self.epochs_before_close())
+            .is_some_and(|closable_epoch| closable_epoch <= current_ncn_epoch)
+    }
+
+    pub fn find_price_feed(&self, mint: &Pubkey) -> Option<&PriceFeedEntry> {
+        self.price_feeds
+            .iter()
+            .find(|entry| !entry.is_empty() && entry.mint == *mint)
+    }
+
+    /// Adds or replaces the price feed of a mint. The staleness and confidence limits must be
+    /// at least 1 and at most `PriceFeedEntry::MAX_STALE_SLOTS` and
+    /// `PriceFeedEntry::MAX_CONFIDENCE_BPS`, so a feed can neither be unusable nor unchecked
+    pub fn set_price_feed(&mut self, price_feed: PriceFeedEntry) -> Result<(), WeightTableError> {
+        let max_stale_slots = price_feed.max_stale_slots();
+        if max_stale_slots == 0 || max_stale_slots > PriceFeedEntry::MAX_STALE_SLOTS {
+            return Err(WeightTableError::InvalidMaxStaleSlots);
+        }
+
+        let max_confidence_bps = price_feed.max_confidence_bps();
+        if max_confidence_bps == 0 || max_confidence_bps > PriceFeedEntry::MAX_CONFIDENCE_BPS {
+            return Err(WeightTableError::InvalidMaxConfidenceBps);
+        }
+
+        let entry = self
+            .price_feeds
+            .iter_mut()
+            .find(|entry| entry.mint == price_feed.mint || entry.is_empty());
+
+        match entry {
+            Some(entry) => *entry = price_feed,
+            None => return Err(WeightTableError::NoMoreTableSlots),
+        }
+
+        Ok(())
+    }
+
+    pub fn seeds(ncn: &Pubkey) -> Vec<Vec<u8>> {
+        Vec::from_iter(
+            [b"WEIGHT_TABLE_CONFIG".to_vec(), ncn.to_bytes().to_vec()]
+                .iter()
+                .cloned(),
+        )
+    }
+
+    pub fn find_program_address(program_id: &Pubkey, ncn: &Pubkey) -> (Pubkey, u8, Vec<Vec<u8>>) {
+        let seeds = Self::seeds(ncn);
+        let seeds_iter: Vec<_> = seeds.iter().map(|s| s.as_slice()).collect();
+        let (pda, bump) = Pubkey::find_program_address(&seeds_iter, program_id);
+        (pda, bump, seeds)
+    }
+
+    pub fn check_weight_table_admin(
+        &self,
+        weight_table_admin: &Pubkey,
+    ) -> Result<(), WeightTableError> {
+        if self.weight_table_admin.ne(weight_table_admin) {
+            msg!("Incorrect weight table admin");
+            return Err(WeightTableError::IncorrectWeightTableAdmin);
+        }
+        Ok(())
+    }
+
+    pub fn load(
+        program_id: &Pubkey,
+        weight_table_config: &AccountInfo,
+        ncn: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<(), ProgramError> {
+        if weight_table_config.owner.ne(program_id) {
+            msg!("Weight table config account is not owned by the program");
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if weight_table_config.data_is_empty() {
+            msg!("Weight table config is empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if expect_writable && !weight_table_config.is_writable {
+            msg!("Weight table config account is not writable");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if weight_table_config.data.borrow()[0].ne(&Self::DISCRIMINATOR) {
+            msg!("Weight table config account has an incorrect discriminator");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let expected_pubkey = Self::find_program_address(program_id, ncn.key).0;
+        if weight_table_config.key.ne(&expected_pubkey) {
+            msg!("Weight table config incorrect PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Zeroable, ShankType, Pod)]
+#[repr(C)]
+pub struct PriceFeedEntry {
+    pub mint: Pubkey,
+    /// Switchboard pull feed pricing the mint in SOL
+    pub price_feed: Pubkey,
+    /// How many slots old the feed's result can be
+    pub max_stale_slots: PodU64,
+    /// How wide the feed's standard deviation can be, in bps of the price
+    pub max_confidence_bps: PodU64,
+}
+
+impl PriceFeedEntry {
+    /// About an hour of slots
+    pub const MAX_STALE_SLOTS: u64 = 9_000;
+    /// 10% of the price
+    pub const MAX_CONFIDENCE_BPS: u64 = 1_000;
+
+    pub fn new(
+        mint: Pubkey,
+        price_feed: Pubkey,
+        max_stale_slots: u64,
+        max_confidence_bps: u64,
+    ) -> Self {
+        Self {
+            mint,
+            price_feed,
+            max_stale_slots: PodU64::from(max_stale_slots),
+            max_confidence_bps: PodU64::from(max_confidence_bps),
+        }
+    }
+
+    pub fn max_stale_slots(&self) -> u64 {
+        self.max_stale_slots.into()
+    }
+
+    pub fn max_confidence_bps(&self) -> u64 {
+        self.max_confidence_bps.into()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mint.eq(&Pubkey::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_weight_table_admin() {
+        let weight_table_admin = Pubkey::new_unique();
+        let config = WeightTableConfig::new(Pubkey::new_unique(), weight_table_admin, 0);
+
+        assert!(config.check_weight_table_admin(&weight_table_admin).is_ok());
+        assert_eq!(
+            config.check_weight_table_admin(&Pubkey::new_unique()),
+            Err(WeightTableError::IncorrectWeightTableAdmin)
+        );
+    }
+
+    #[test]
+    fn test_can_close_weight_table() {
+        let mut config = WeightTableConfig::new(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        assert_eq!(
+            config.epochs_before_close(),
+            WeightTableConfig::DEFAULT_EPOCHS_BEFORE_CLOSE
+        );
+
+        config.set_epochs_before_close(3).unwrap();
+        assert!(!config.can_close_weight_table(10, 10));
+        assert!(!config.can_close_weight_table(10, 12));
+        assert!(config.can_close_weight_table(10, 13));
+        assert!(config.can_close_weight_table(10, 20));
+        assert!(!config.can_close_weight_table(u64::MAX, u64::MAX));
+
+        assert_eq!(
+            config.set_epochs_before_close(0),
+            Err(WeightTableError::InvalidEpochsBeforeClose)
+        );
+        assert_eq!(config.epochs_before_close(), 3);
+    }
+
+    #[test]
+    fn test_set_price_feed() {
+        let mut config = WeightTableConfig::new(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        let mint = Pubkey::new_unique();
+
+        let feed = PriceFeedEntry::new(mint, Pubkey::new_unique(), 10, 50);
+        config.set_price_feed(feed).unwrap();
+        assert_eq!(config.find_price_feed(&mint), Some(&feed));
+
+        // Replacing the feed keeps a single entry for the mint
+        let new_feed = PriceFeedEntry::new(mint, Pubkey::new_unique(), 20, 100);
+        config.set_price_feed(new_feed).unwrap();
+        assert_eq!(config.find_price_feed(&mint), Some(&new_feed));
+        assert_eq!(
+            config
+                .price_feeds
+                .iter()
+                .filter(|entry| !entry.is_empty())
+                .count(),
+            1
+        );
+
+        for _ in 0..WeightTableConfig::MAX_PRICE_FEEDS - 1 {
+            config
+                .set_price_feed(PriceFeedEntry::new(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    10,
+                    50,
+                ))
+                .unwrap();
+        }
+        assert_eq!(
+            config.set_price_feed(PriceFeedEntry::new(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                10,
+                50,
+            )),
+            Err(WeightTableError::NoMoreTableSlots)
+        );
+        assert_eq!(config.find_price_feed(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn test_set_price_feed_bounds() {
+        let mut config = WeightTableConfig::new(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        let mint = Pubkey::new_unique();
+        let price_feed = Pubkey::new_unique();
+
+        for max_stale_slots in [0, PriceFeedEntry::MAX_STALE_SLOTS + 1, u64::MAX] {
+            assert_eq!(
+                config.set_price_feed(PriceFeedEntry::new(mint, price_feed, max_stale_slots, 50)),
+                Err(WeightTableError::InvalidMaxStaleSlots)
+            );
+        }
+
+        for max_confidence_bps in [0, PriceFeedEntry::MAX_CONFIDENCE_BPS + 1, u64::MAX] {
+            assert_eq!(
+                config.set_price_feed(PriceFeedEntry::new(
+                    mint,
+                    price_feed,
+                    10,
+                    max_confidence_bps
+                )),
+                Err(WeightTableError::InvalidMaxConfidenceBps)
+            );
+        }
+        assert_eq!(config.find_price_feed(&mint), None);
+
+        let feed = PriceFeedEntry::new(
+            mint,
+            price_feed,
+            PriceFeedEntry::MAX_STALE_SLOTS,
+            PriceFeedEntry::MAX_CONFIDENCE_BPS,
+        );
+        config.set_price_feed(feed).unwrap();
+        assert_eq!(config.find_price_feed(&mint), Some(&feed));
+    }
+}
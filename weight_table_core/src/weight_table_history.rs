@@ -0,0 +1,473 @@
+use bytemuck::{Pod, Zeroable};
+use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use shank::{ShankAccount, ShankType};
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    discriminators::WEIGHT_TABLE_HISTORY_DISCRIMINATOR,
+    error::WeightTableError,
+    weight::Weight,
+    weight_table::{WeightEntry, WeightTable},
+};
+
+// PDA'd ["WEIGHT_TABLE_HISTORY", NCN]
+#[derive(Debug, Clone, Copy, Zeroable, ShankType, Pod, AccountDeserialize, ShankAccount)]
+#[repr(C)]
+pub struct WeightTableHistory {
+    /// The NCN the history belongs to
+    pub ncn: Pubkey,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+
+    /// Total snapshots written, the first `MAX_SNAPSHOTS` fill the slots in order
+    snapshot_count: PodU64,
+
+    /// Reserved space
+    reserved: [u8; 127],
+
+    /// The most recently finalized weight tables, in storage order
+    snapshots: [WeightTableSnapshot; 32],
+}
+
+impl Discriminator for WeightTableHistory {
+    const DISCRIMINATOR: u8 = WEIGHT_TABLE_HISTORY_DISCRIMINATOR;
+}
+
+impl WeightTableHistory {
+    /// Larger than an account created through a CPI (10KB), the account is created at that size
+    /// and grown with ReallocWeightTableHistory before it is initialized
+    pub const MAX_SNAPSHOTS: usize = 32;
+
+    pub fn new(ncn: Pubkey, bump: u8) -> Self {
+        Self {
+            ncn,
+            bump,
+            snapshot_count: PodU64::from(0),
+            reserved: [0; 127],
+            snapshots: [WeightTableSnapshot::default(); Self::MAX_SNAPSHOTS],
+        }
+    }
+
+    pub fn size() -> u64 {
+        8_u64.checked_add(size_of::<Self>() as u64).unwrap()
+    }
+
+    pub fn snapshot_count(&self) -> u64 {
+        self.snapshot_count.into()
+    }
+
+    /// The snapshots written so far, in storage order. Includes snapshots of reopened tables,
+    /// see `WeightTableSnapshot::is_reopened`
+    pub fn snapshots(&self) -> &[WeightTableSnapshot] {
+        let len = (self.snapshot_count() as usize).min(Self::MAX_SNAPSHOTS);
+        &self.snapshots[..len]
+    }
+
+    /// The snapshot of `ncn_epoch`, unless its table was reopened and not finalized again
+    pub fn find_snapshot(&self, ncn_epoch: u64) -> Option<&WeightTableSnapshot> {
+        self.snapshots()
+            .iter()
+            .find(|snapshot| snapshot.ncn_epoch() == ncn_epoch && !snapshot.is_reopened())
+    }
+
+    pub fn find_weight(&self, ncn_epoch: u64, mint: &Pubkey) -> Option<Weight> {
+        self.find_snapshot(ncn_epoch)
+            .and_then(|snapshot| snapshot.find_weight(mint))
+    }
+
+    /// Records a finalized weight table, replacing the snapshot of the same epoch if the
+    /// table was reopened and finalized again. Once full, the snapshot of the oldest epoch is
+    /// overwritten, a table older than every held snapshot is ignored so it never evicts a
+    /// newer epoch.
+    pub fn record(&mut self, weight_table: &WeightTable) -> Result<(), WeightTableError> {
+        let snapshot = WeightTableSnapshot::new(weight_table);
+        let len = self.snapshots().len();
+
+        if let Some(existing) = self.snapshots[..len]
+            .iter_mut()
+            .find(|existing| existing.ncn_epoch() == snapshot.ncn_epoch())
+        {
+            *existing = snapshot;
+            return Ok(());
+        }
+
+        let index = if len < Self::MAX_SNAPSHOTS {
+            len
+        } else {
+            let (oldest_index, oldest) = self
+                .snapshots
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, existing)| existing.ncn_epoch())
+                .ok_or(WeightTableError::ArithmeticOverflow)?;
+
+            if snapshot.ncn_epoch() < oldest.ncn_epoch() {
+                msg!(
+                    "Weight table for epoch {} is older than the history, not recorded",
+                    snapshot.ncn_epoch()
+                );
+                return Ok(());
+            }
+
+            oldest_index
+        };
+
+        self.snapshots[index] = snapshot;
+        self.snapshot_count = PodU64::from(
+            self.snapshot_count()
+                .checked_add(1)
+                .ok_or(WeightTableError::ArithmeticOverflow)?,
+        );
+
+        Ok(())
+    }
+
+    /// Flags the snapshot of `ncn_epoch` as stale once its table is reopened, it is replaced
+    /// when the table is finalized again
+    pub fn mark_reopened(&mut self, ncn_epoch: u64) {
+        let len = self.snapshots().len();
+        if let Some(snapshot) = self.snapshots[..len]
+            .iter_mut()
+            .find(|snapshot| snapshot.ncn_epoch() == ncn_epoch)
+        {
+            snapshot.slot_finalized = PodU64::from(0);
+        }
+    }
+
+    pub fn seeds(ncn: &Pubkey) -> Vec<Vec<u8>> {
+        Vec::from_iter(
+            [b"WEIGHT_TABLE_HISTORY".to_vec(), ncn.to_bytes().to_vec()]
+                .iter()
+                .cloned(),
+        )
+    }
+
+    pub fn find_program_address(program_id: &Pubkey, ncn: &Pubkey) -> (Pubkey, u8, Vec<Vec<u8>>) {
+        let seeds = Self::seeds(ncn);
+        let seeds_iter: Vec<_> = seeds.iter().map(|s| s.as_slice()).collect();
+        let (pda, bump) = Pubkey::find_program_address(&seeds_iter, program_id);
+        (pda, bump, seeds)
+    }
+
+    pub fn load(
+        program_id: &Pubkey,
+        weight_table_history: &AccountInfo,
+        ncn: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<(), ProgramError> {
+        if weight_table_history.owner.ne(program_id) {
+            msg!("Weight table history account is not owned by the program");
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if weight_table_history.data_is_empty() {
+            msg!("Weight table history is empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if expect_writable && !weight_table_history.is_writable {
+            msg!("Weight table history account is not writable");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if weight_table_history.data.borrow()[0].ne(&Self::DISCRIMINATOR) {
+            msg!("Weight table history account has an incorrect discriminator");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let expected_pubkey = Self::find_program_address(program_id, ncn.key).0;
+        if weight_table_history.key.ne(&expected_pubkey) {
+            msg!("Weight table history incorrect PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Loads the history if it was created and grown to full size, returns false if it is
+    /// not initialized yet. The history is optional, weight tables work without it.
+    pub fn load_if_initialized(
+        program_id: &Pubkey,
+        weight_table_history: &AccountInfo,
+        ncn: &AccountInfo,
+        expect_writable: bool,
+    ) -> Result<bool, ProgramError> {
+        let expected_pubkey = Self::find_program_address(program_id, ncn.key).0;
+        if weight_table_history.key.ne(&expected_pubkey) {
+            msg!("Weight table history incorrect PDA");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let initialized = weight_table_history.owner.eq(program_id)
+            && !weight_table_history.data_is_empty()
+            && weight_table_history.data.borrow()[0].eq(&Self::DISCRIMINATOR);
+        if !initialized {
+            msg!("Weight table history is not initialized");
+            return Ok(false);
+        }
+
+        Self::load(program_id, weight_table_history, ncn, expect_writable)?;
+        Ok(true)
+    }
+}
+
+/// The entries of a finalized weight table
+#[derive(Default, Debug, Clone, Copy, Zeroable, ShankType, Pod)]
+#[repr(C)]
+pub struct WeightTableSnapshot {
+    ncn_epoch: PodU64,
+    slot_finalized: PodU64,
+    pub table: [WeightEntry; 32],
+}
+
+impl WeightTableSnapshot {
+    pub fn new(weight_table: &WeightTable) -> Self {
+        Self {
+            ncn_epoch: weight_table.ncn_epoch,
+            slot_finalized: PodU64::from(weight_table.slot_finalized()),
+            table: weight_table.table,
+        }
+    }
+
+    pub fn ncn_epoch(&self) -> u64 {
+        self.ncn_epoch.into()
+    }
+
+    pub fn slot_finalized(&self) -> u64 {
+        self.slot_finalized.into()
+    }
+
+    /// The table was reopened after this snapshot was taken, so its weights may be stale
+    pub fn is_reopened(&self) -> bool {
+        self.slot_finalized() == 0
+    }
+
+    pub fn find_weight(&self, mint: &Pubkey) -> Option<Weight> {
+        self.table
+            .iter()
+            .find(|entry| !entry.is_empty() && entry.mint == *mint)
+            .map(|entry| entry.weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finalized_table(ncn: Pubkey, ncn_epoch: u64, mint: &Pubkey, numerator: u64) -> WeightTable {
+        let mut table = WeightTable::new(ncn, ncn_epoch, 0, 0);
+        table
+            .set_weight(mint, Weight::new(numerator, 1).unwrap())
+            .unwrap();
+        table.finalize(ncn_epoch + 1);
+        table
+    }
+
+    #[test]
+    fn test_weight_table_history_size() {
+        // Created at 10KB and grown by at most 10KB per realloc
+        assert!(WeightTableHistory::size() > 10_240);
+        assert_eq!(WeightTableHistory::size().div_ceil(10_240), 5);
+    }
+
+    #[test]
+    fn test_weight_table_history_ring() {
+        let ncn = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut history = WeightTableHistory::new(ncn, 0);
+        assert!(history.snapshots().is_empty());
+        assert!(history.find_snapshot(0).is_none());
+
+        for ncn_epoch in 0..WeightTableHistory::MAX_SNAPSHOTS as u64 {
+            history
+                .record(&finalized_table(ncn, ncn_epoch, &mint, ncn_epoch + 1))
+                .unwrap();
+        }
+        assert_eq!(history.snapshots().len(), WeightTableHistory::MAX_SNAPSHOTS);
+        assert_eq!(
+            history.find_weight(0, &mint),
+            Some(Weight::new(1, 1).unwrap())
+        );
+        assert_eq!(history.find_snapshot(3).unwrap().slot_finalized(), 4);
+
+        // The oldest epoch is overwritten once the history is full
+        let ncn_epoch = WeightTableHistory::MAX_SNAPSHOTS as u64;
+        history
+            .record(&finalized_table(ncn, ncn_epoch, &mint, 100))
+            .unwrap();
+        assert_eq!(history.snapshots().len(), WeightTableHistory::MAX_SNAPSHOTS);
+        assert_eq!(history.snapshot_count(), ncn_epoch + 1);
+        assert!(history.find_snapshot(0).is_none());
+        assert_eq!(
+            history.find_weight(ncn_epoch, &mint),
+            Some(Weight::new(100, 1).unwrap())
+        );
+        assert_eq!(
+            history.find_weight(1, &mint),
+            Some(Weight::new(2, 1).unwrap())
+        );
+        assert_eq!(history.find_weight(1, &Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn test_weight_table_history_refinalize_replaces_snapshot() {
+        let ncn = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut history = WeightTableHistory::new(ncn, 0);
+
+        history.record(&finalized_table(ncn, 5, &mint, 1)).unwrap();
+        history.record(&finalized_table(ncn, 5, &mint, 2)).unwrap();
+
+        assert_eq!(history.snapshot_count(), 1);
+        assert_eq!(
+            history.find_weight(5, &mint),
+            Some(Weight::new(2, 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_load_if_initialized() {
+        let program_id = Pubkey::new_unique();
+        let ncn_pubkey = Pubkey::new_unique();
+        let (history_pubkey, _, _) =
+            WeightTableHistory::find_program_address(&program_id, &ncn_pubkey);
+
+        let mut ncn_lamports = 0;
+        let mut ncn_data = [];
+        let ncn = AccountInfo::new(
+            &ncn_pubkey,
+            false,
+            false,
+            &mut ncn_lamports,
+            &mut ncn_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Not created yet
+        let system_program = solana_program::system_program::id();
+        let mut lamports = 0;
+        let mut data = [];
+        let history = AccountInfo::new(
+            &history_pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program,
+            false,
+            0,
+        );
+        assert_eq!(
+            WeightTableHistory::load_if_initialized(&program_id, &history, &ncn, true),
+            Ok(false)
+        );
+
+        // Created but still growing to its full size
+        let mut lamports = 0;
+        let mut data = vec![0; 10_240];
+        let history = AccountInfo::new(
+            &history_pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert_eq!(
+            WeightTableHistory::load_if_initialized(&program_id, &history, &ncn, true),
+            Ok(false)
+        );
+
+        let mut lamports = 0;
+        let mut data = vec![0; WeightTableHistory::size() as usize];
+        data[0] = WeightTableHistory::DISCRIMINATOR;
+        let history = AccountInfo::new(
+            &history_pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert_eq!(
+            WeightTableHistory::load_if_initialized(&program_id, &history, &ncn, true),
+            Ok(true)
+        );
+
+        // Any other account is rejected
+        let other_pubkey = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        let other = AccountInfo::new(
+            &other_pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program,
+            false,
+            0,
+        );
+        assert_eq!(
+            WeightTableHistory::load_if_initialized(&program_id, &other, &ncn, true),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_weight_table_history_reopened_snapshot() {
+        let ncn = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut history = WeightTableHistory::new(ncn, 0);
+
+        history.record(&finalized_table(ncn, 5, &mint, 1)).unwrap();
+        history.record(&finalized_table(ncn, 6, &mint, 1)).unwrap();
+
+        history.mark_reopened(5);
+        assert!(history.find_snapshot(5).is_none());
+        assert!(history.snapshots()[0].is_reopened());
+        assert!(history.find_snapshot(6).is_some());
+
+        // Finalizing the table again replaces the stale snapshot
+        history.record(&finalized_table(ncn, 5, &mint, 2)).unwrap();
+        assert_eq!(history.snapshot_count(), 2);
+        assert_eq!(
+            history.find_weight(5, &mint),
+            Some(Weight::new(2, 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_weight_table_history_ignores_evicted_epochs() {
+        let ncn = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut history = WeightTableHistory::new(ncn, 0);
+
+        // Epochs 10..=41 fill the history, recorded out of order
+        for ncn_epoch in (10..10 + WeightTableHistory::MAX_SNAPSHOTS as u64).rev() {
+            history
+                .record(&finalized_table(ncn, ncn_epoch, &mint, 1))
+                .unwrap();
+        }
+
+        // Epoch 42 evicts epoch 10, wherever it is stored
+        history.record(&finalized_table(ncn, 42, &mint, 2)).unwrap();
+        assert!(history.find_snapshot(10).is_none());
+        assert_eq!(
+            history.find_weight(42, &mint),
+            Some(Weight::new(2, 1).unwrap())
+        );
+
+        // Re-finalizing an evicted epoch does not overwrite a newer one
+        history.record(&finalized_table(ncn, 10, &mint, 3)).unwrap();
+        history.record(&finalized_table(ncn, 5, &mint, 3)).unwrap();
+        assert!(history.find_snapshot(10).is_none());
+        assert!(history.find_snapshot(5).is_none());
+        assert_eq!(history.snapshots().len(), WeightTableHistory::MAX_SNAPSHOTS);
+        for ncn_epoch in 11..=42 {
+            assert!(history.find_snapshot(ncn_epoch).is_some(), "{}", ncn_epoch);
+        }
+    }
+}
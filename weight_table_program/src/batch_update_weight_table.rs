@@ -0,0 +1,74 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::{
+    error::WeightTableError, weight::Weight, weight_table::WeightTable,
+    weight_table_config::WeightTableConfig,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::token_helpers::load_mint_decimals;
+
+/// Updates the weights of many mints at once
+/// The mints are the remaining accounts, in the same order as `weights`
+pub fn process_batch_update_weight_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ncn_epoch: u64,
+    weights: Vec<(u64, u64)>,
+) -> ProgramResult {
+    let [ncn, weight_table_config, weight_table, weight_table_admin, restaking_program_id, mints @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+
+    load_signer(weight_table_admin, true)?;
+    WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if mints.len() != weights.len() {
+        msg!(
+            "Expected {} mint accounts, received {}",
+            weights.len(),
+            mints.len()
+        );
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
+    }
+
+    let mut weight_table_data = weight_table.try_borrow_mut_data()?;
+    let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
+
+    if weight_table_account.finalized() {
+        msg!("Weight table is finalized");
+        return Err(WeightTableError::WeightTableFinalized.into());
+    }
+
+    // Any failure reverts the whole batch
+    for (mint, (weight_numerator, weight_denominator)) in mints.iter().zip(weights) {
+        load_mint_decimals(mint)?;
+
+        let weight = Weight::new(weight_numerator, weight_denominator)?;
+
+        weight_table_account.set_weight(mint.key, weight)?;
+    }
+
+    Ok(())
+}
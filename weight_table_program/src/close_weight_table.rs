@@ -0,0 +1,108 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::{close_program_account, loader::load_signer};
+use jito_restaking_core::{config::Config, ncn::Ncn};
+use jito_reward_core::epoch_reward_merkle_root::EpochRewardMerkleRoot;
+use jito_weight_table_core::{
+    error::WeightTableError, weight_table::WeightTable, weight_table_config::WeightTableConfig,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar,
+};
+
+use crate::REWARD_PROGRAM_ID;
+
+/// Closes a Weight Table older than the config's `epochs_before_close`
+/// The table can't be closed while its reward epoch is still being voted on, it is no longer
+/// read once voting is finalized
+pub fn process_close_weight_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ncn_epoch: u64,
+) -> ProgramResult {
+    let [restaking_config, ncn, weight_table_config, weight_table, epoch_reward_merkle_root, weight_table_admin, receiver, restaking_program_id] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    Config::load(restaking_program_id.key, restaking_config, false)?;
+    let ncn_epoch_length = {
+        let config_data = restaking_config.data.borrow();
+        let config = Config::try_from_slice_unchecked(&config_data)?;
+        config.epoch_length()
+    };
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+
+    load_signer(weight_table_admin, false)?;
+    WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !receiver.is_writable {
+        msg!("Receiver account is not writable");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let current_ncn_epoch = Clock::get()?
+        .slot
+        .checked_div(ncn_epoch_length)
+        .ok_or(WeightTableError::DenominatorIsZero)?;
+
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
+
+        if !weight_table_config.can_close_weight_table(ncn_epoch, current_ncn_epoch) {
+            msg!(
+                "Weight table for epoch {} can be closed {} epochs later, current epoch is {}",
+                ncn_epoch,
+                weight_table_config.epochs_before_close(),
+                current_ncn_epoch
+            );
+            return Err(WeightTableError::WeightTableNotClosable.into());
+        }
+    }
+
+    let (epoch_reward_merkle_root_pubkey, _, _) =
+        EpochRewardMerkleRoot::find_program_address(&REWARD_PROGRAM_ID, ncn.key, ncn_epoch);
+    if epoch_reward_merkle_root
+        .key
+        .ne(&epoch_reward_merkle_root_pubkey)
+    {
+        msg!("Incorrect epoch reward merkle root PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // An epoch that never initialized its merkle root has no rewards referencing the table
+    if !(epoch_reward_merkle_root.owner.eq(&system_program::id())
+        && epoch_reward_merkle_root.data_is_empty())
+    {
+        EpochRewardMerkleRoot::load(&REWARD_PROGRAM_ID, ncn.key, epoch_reward_merkle_root, false)?;
+
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+
+        if epoch_reward_merkle_root.needs_weight_table() {
+            msg!("Weight table is referenced by a reward epoch that is still voting");
+            return Err(WeightTableError::WeightTableReferenced.into());
+        }
+    }
+
+    msg!(
+        "Closing weight table {} for epoch {}",
+        weight_table.key,
+        ncn_epoch
+    );
+    close_program_account(program_id, weight_table, receiver)?;
+
+    Ok(())
+}
@@ -1,48 +1,146 @@
 use jito_bytemuck::AccountDeserialize;
 use jito_jsm_core::loader::load_signer;
-use jito_restaking_core::ncn::Ncn;
-use jito_weight_table_core::{error::WeightTableError, weight_table::WeightTable};
+use jito_restaking_core::{config::Config, ncn::Ncn, ncn_vault_ticket::NcnVaultTicket};
+use jito_vault_core::vault::Vault;
+use jito_vault_program::ID as VAULT_PROGRAM_ID;
+use jito_weight_table_core::{
+    error::WeightTableError, weight_table::WeightTable, weight_table_config::WeightTableConfig,
+    weight_table_history::WeightTableHistory,
+};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
     program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
 };
 
-/// Initializes a Weight Table
+/// Finalizes a Weight Table
+/// Every vault of the NCN is passed as a (ncn_vault_ticket, vault) pair in the remaining
+/// accounts, and every active vault's supported mint needs a non-zero weight so no
+/// operator's stake is ignored in reward consensus. The finalized table is recorded in the
+/// NCN's Weight Table History once it is initialized, finalizing does not wait for it
 pub fn process_finalize_weight_table(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     ncn_epoch: u64,
 ) -> ProgramResult {
-    let [ncn, weight_table, weight_table_admin, restaking_program_id] = accounts else {
+    let [restaking_config, ncn, weight_table_config, weight_table, weight_table_history, weight_table_admin, restaking_program_id, vault_accounts @ ..] =
+        accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    Ncn::load(restaking_program_id.key, ncn, false)?;
-    let ncn_weight_table_admin = {
-        //TODO switch to weight table admin when that is merged
-        let ncn_data = ncn.data.borrow();
-        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
-        ncn.admin
+    Config::load(restaking_program_id.key, restaking_config, false)?;
+    let ncn_epoch_length = {
+        let config_data = restaking_config.data.borrow();
+        let config = Config::try_from_slice_unchecked(&config_data)?;
+        config.epoch_length()
     };
 
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+
     load_signer(weight_table_admin, true)?;
     WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
+    let record_history =
+        WeightTableHistory::load_if_initialized(program_id, weight_table_history, ncn, true)?;
 
     if restaking_program_id.key.ne(&jito_restaking_program::id()) {
         msg!("Incorrect restaking program ID");
         return Err(ProgramError::InvalidAccountData);
     }
 
-    if ncn_weight_table_admin.ne(&weight_table_admin.key) {
-        msg!("Vault update delegations ticket is not at the correct PDA");
-        return Err(WeightTableError::IncorrectWeightTableAdmin.into());
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
+    }
+
+    if vault_accounts.len() % 2 != 0 {
+        msg!("Vault accounts must be (ncn_vault_ticket, vault) pairs");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let vault_count = {
+        let ncn_data = ncn.data.borrow();
+        let ncn_account = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        ncn_account.vault_count()
+    };
+
+    if (vault_accounts.len() / 2) as u64 != vault_count {
+        msg!(
+            "Expected {} vaults, received {}",
+            vault_count,
+            vault_accounts.len() / 2
+        );
+        return Err(WeightTableError::VaultCountMismatch.into());
     }
 
     let mut weight_table_data = weight_table.try_borrow_mut_data()?;
     let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
 
+    if weight_table_account.finalized() {
+        msg!("Weight table is finalized");
+        return Err(WeightTableError::WeightTableFinalized.into());
+    }
+
     let current_slot = Clock::get()?.slot;
+
+    // Ticket indices are unique per NCN, so `vault_count` distinct indices cover every vault
+    let mut seen_vaults = vec![false; vault_count as usize];
+    let mut remaining = vault_accounts;
+    while let [ncn_vault_ticket, vault, rest @ ..] = remaining {
+        remaining = rest;
+
+        NcnVaultTicket::load(
+            restaking_program_id.key,
+            ncn_vault_ticket,
+            ncn,
+            vault,
+            false,
+        )?;
+        Vault::load(&VAULT_PROGRAM_ID, vault, false)?;
+
+        let ncn_vault_ticket_data = ncn_vault_ticket.data.borrow();
+        let ncn_vault_ticket_account =
+            NcnVaultTicket::try_from_slice_unchecked(&ncn_vault_ticket_data)?;
+
+        let seen = seen_vaults
+            .get_mut(ncn_vault_ticket_account.index() as usize)
+            .ok_or(WeightTableError::VaultCountMismatch)?;
+        if *seen {
+            msg!("Duplicate vault {}", vault.key);
+            return Err(WeightTableError::DuplicateVault.into());
+        }
+        *seen = true;
+
+        if !ncn_vault_ticket_account
+            .state
+            .is_active(current_slot, ncn_epoch_length)
+        {
+            continue;
+        }
+
+        let vault_data = vault.data.borrow();
+        let vault_account = Vault::try_from_slice_unchecked(&vault_data)?;
+
+        if !weight_table_account.has_nonzero_weight(&vault_account.supported_mint) {
+            msg!(
+                "Vault {} supported mint {} has no weight",
+                vault.key,
+                vault_account.supported_mint
+            );
+            return Err(WeightTableError::MissingVaultMintWeight.into());
+        }
+    }
+
     weight_table_account.finalize(current_slot);
 
+    if record_history {
+        let mut weight_table_history_data = weight_table_history.try_borrow_mut_data()?;
+        let weight_table_history_account =
+            WeightTableHistory::try_from_slice_unchecked_mut(&mut weight_table_history_data)?;
+        weight_table_history_account.record(weight_table_account)?;
+    }
+
     Ok(())
 }
@@ -4,7 +4,9 @@ use jito_jsm_core::{
     loader::{load_signer, load_system_account, load_system_program},
 };
 use jito_restaking_core::{config::Config, ncn::Ncn};
-use jito_weight_table_core::{error::WeightTableError, weight_table::WeightTable};
+use jito_weight_table_core::{
+    error::WeightTableError, weight_table::WeightTable, weight_table_config::WeightTableConfig,
+};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
     program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
@@ -17,7 +19,7 @@ pub fn process_initialize_weight_table(
     accounts: &[AccountInfo],
     first_slot_of_ncn_epoch: Option<u64>,
 ) -> ProgramResult {
-    let [restaking_config, ncn, weight_table, weight_table_admin, restaking_program_id, system_program] =
+    let [restaking_config, ncn, weight_table_config, weight_table, weight_table_admin, restaking_program_id, system_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -30,12 +32,7 @@ pub fn process_initialize_weight_table(
     };
 
     Ncn::load(restaking_program_id.key, ncn, false)?;
-    let ncn_weight_table_admin = {
-        //TODO switch to weight table admin when that is merged
-        let ncn_data = ncn.data.borrow();
-        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
-        ncn.admin
-    };
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
 
     load_system_account(weight_table, true)?;
     load_signer(weight_table_admin, true)?;
@@ -46,9 +43,11 @@ pub fn process_initialize_weight_table(
         return Err(ProgramError::InvalidAccountData);
     }
 
-    if ncn_weight_table_admin.ne(&weight_table_admin.key) {
-        msg!("Vault update delegations ticket is not at the correct PDA");
-        return Err(WeightTableError::IncorrectWeightTableAdmin.into());
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
     }
 
     let current_slot = Clock::get()?.slot;
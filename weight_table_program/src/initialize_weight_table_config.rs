@@ -0,0 +1,82 @@
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{
+    create_account,
+    loader::{load_signer, load_system_account, load_system_program},
+};
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::{error::WeightTableError, weight_table_config::WeightTableConfig};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+};
+
+/// Initializes the NCN's Weight Table Config
+/// Only the NCN admin can create it and choose the weight table admin
+pub fn process_initialize_weight_table_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [ncn, weight_table_config, ncn_admin, weight_table_admin, restaking_program_id, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    let ncn_admin_pubkey = {
+        let ncn_data = ncn.data.borrow();
+        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        ncn.admin
+    };
+
+    load_system_account(weight_table_config, true)?;
+    load_signer(ncn_admin, true)?;
+    load_system_program(system_program)?;
+
+    if ncn_admin_pubkey.ne(ncn_admin.key) {
+        msg!("Incorrect NCN admin");
+        return Err(WeightTableError::IncorrectNcnAdmin.into());
+    }
+
+    let (weight_table_config_pubkey, weight_table_config_bump, mut weight_table_config_seeds) =
+        WeightTableConfig::find_program_address(program_id, ncn.key);
+    weight_table_config_seeds.push(vec![weight_table_config_bump]);
+
+    if weight_table_config_pubkey.ne(weight_table_config.key) {
+        msg!("Incorrect weight table config PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    msg!(
+        "Initializing Weight Table Config {} for NCN: {} with admin: {}",
+        weight_table_config.key,
+        ncn.key,
+        weight_table_admin.key
+    );
+    create_account(
+        ncn_admin,
+        weight_table_config,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        8_u64
+            .checked_add(size_of::<WeightTableConfig>() as u64)
+            .unwrap(),
+        &weight_table_config_seeds,
+    )?;
+
+    let mut weight_table_config_data = weight_table_config.try_borrow_mut_data()?;
+    weight_table_config_data[0] = WeightTableConfig::DISCRIMINATOR;
+    let weight_table_config_account =
+        WeightTableConfig::try_from_slice_unchecked_mut(&mut weight_table_config_data)?;
+
+    *weight_table_config_account =
+        WeightTableConfig::new(*ncn.key, *weight_table_admin.key, weight_table_config_bump);
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{
+    create_account,
+    loader::{load_signer, load_system_account, load_system_program},
+};
+use jito_restaking_core::{config::Config, ncn::Ncn};
+use jito_weight_table_core::{
+    error::WeightTableError, weight_table::WeightTable, weight_table_config::WeightTableConfig,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+};
+
+/// Initializes a Weight Table with the entries of an earlier, finalized Weight Table
+/// The weight table admin only needs to update the weights that changed before finalizing
+pub fn process_initialize_weight_table_from_previous(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    previous_ncn_epoch: u64,
+    first_slot_of_ncn_epoch: Option<u64>,
+) -> ProgramResult {
+    let [restaking_config, ncn, weight_table_config, previous_weight_table, weight_table, weight_table_admin, restaking_program_id, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    Config::load(restaking_program_id.key, restaking_config, false)?;
+    let ncn_epoch_length = {
+        let config_data = restaking_config.data.borrow();
+        let config = Config::try_from_slice_unchecked(&config_data)?;
+        config.epoch_length()
+    };
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+    WeightTable::load(
+        program_id,
+        previous_weight_table,
+        ncn,
+        previous_ncn_epoch,
+        false,
+    )?;
+
+    load_system_account(weight_table, true)?;
+    load_signer(weight_table_admin, true)?;
+    load_system_program(system_program)?;
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
+    }
+
+    let current_slot = Clock::get()?.slot;
+    let current_ncn_epoch = current_slot
+        .checked_div(ncn_epoch_length)
+        .ok_or(WeightTableError::DenominatorIsZero)?;
+
+    let ncn_epoch_slot = first_slot_of_ncn_epoch.unwrap_or(current_slot);
+    let ncn_epoch = ncn_epoch_slot
+        .checked_div(ncn_epoch_length)
+        .ok_or(WeightTableError::DenominatorIsZero)?;
+
+    if ncn_epoch > current_ncn_epoch {
+        msg!("Weight tables can only be initialized for current or past epochs");
+        return Err(WeightTableError::CannotCreateFutureWeightTables.into());
+    }
+
+    if previous_ncn_epoch >= ncn_epoch {
+        msg!(
+            "Previous weight table epoch {} is not before epoch {}",
+            previous_ncn_epoch,
+            ncn_epoch
+        );
+        return Err(WeightTableError::InvalidPreviousWeightTable.into());
+    }
+
+    let (weight_table_pubkey, weight_table_bump, mut weight_table_seeds) =
+        WeightTable::find_program_address(program_id, ncn.key, ncn_epoch);
+    weight_table_seeds.push(vec![weight_table_bump]);
+
+    if weight_table_pubkey.ne(weight_table.key) {
+        msg!("Incorrect weight table PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let previous_weight_table_data = previous_weight_table.data.borrow();
+    let previous_weight_table_account =
+        WeightTable::try_from_slice_unchecked(&previous_weight_table_data)?;
+
+    if !previous_weight_table_account.finalized() {
+        msg!("Previous weight table is not finalized");
+        return Err(WeightTableError::WeightTableNotFinalized.into());
+    }
+
+    msg!(
+        "Initializing Weight Table {} for NCN: {} at epoch: {} from epoch: {}",
+        weight_table.key,
+        ncn.key,
+        ncn_epoch,
+        previous_ncn_epoch
+    );
+    create_account(
+        weight_table_admin,
+        weight_table,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        8_u64.checked_add(size_of::<WeightTable>() as u64).unwrap(),
+        &weight_table_seeds,
+    )?;
+
+    let mut weight_table_data = weight_table.try_borrow_mut_data()?;
+    weight_table_data[0] = WeightTable::DISCRIMINATOR;
+    let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
+
+    *weight_table_account = WeightTable::new(*ncn.key, ncn_epoch, current_slot, weight_table_bump);
+    weight_table_account.copy_entries_from(previous_weight_table_account);
+
+    Ok(())
+}
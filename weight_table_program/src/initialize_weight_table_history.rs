@@ -0,0 +1,65 @@
+use jito_jsm_core::{
+    create_account,
+    loader::{load_signer, load_system_account, load_system_program},
+};
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::weight_table_history::WeightTableHistory;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::realloc_weight_table_history::initialize_if_full_size;
+
+/// Creates the NCN's Weight Table History at the largest size a CPI allows, it is initialized
+/// once `ReallocWeightTableHistory` grows it to full size
+/// Anyone can pay for it, `FinalizeWeightTable` writes to it
+pub fn process_initialize_weight_table_history(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [ncn, weight_table_history, payer, restaking_program_id, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    load_system_account(weight_table_history, true)?;
+    load_signer(payer, true)?;
+    load_system_program(system_program)?;
+
+    let (weight_table_history_pubkey, weight_table_history_bump, mut weight_table_history_seeds) =
+        WeightTableHistory::find_program_address(program_id, ncn.key);
+    weight_table_history_seeds.push(vec![weight_table_history_bump]);
+
+    if weight_table_history_pubkey.ne(weight_table_history.key) {
+        msg!("Incorrect weight table history PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    msg!(
+        "Initializing Weight Table History {} for NCN: {}",
+        weight_table_history.key,
+        ncn.key
+    );
+    create_account(
+        payer,
+        weight_table_history,
+        system_program,
+        program_id,
+        &Rent::get()?,
+        WeightTableHistory::size().min(MAX_PERMITTED_DATA_INCREASE as u64),
+        &weight_table_history_seeds,
+    )?;
+
+    initialize_if_full_size(weight_table_history, ncn.key, weight_table_history_bump)
+}
@@ -1,5 +1,18 @@
+mod batch_update_weight_table;
+mod close_weight_table;
 mod finalize_weight_table;
 mod initialize_weight_table;
+mod initialize_weight_table_config;
+mod initialize_weight_table_from_previous;
+mod initialize_weight_table_history;
+mod realloc_weight_table_history;
+mod remove_weight;
+mod set_epochs_before_close;
+mod set_price_feed;
+mod set_weight_table_admin;
+mod token_helpers;
+mod unfinalize_weight_table;
+mod update_weight_from_oracle;
 mod update_weight_table;
 
 use borsh::BorshDeserialize;
@@ -13,13 +26,26 @@ use solana_program::{
 use solana_security_txt::security_txt;
 
 use crate::{
+    batch_update_weight_table::process_batch_update_weight_table,
+    close_weight_table::process_close_weight_table,
     finalize_weight_table::process_finalize_weight_table,
     initialize_weight_table::process_initialize_weight_table,
+    initialize_weight_table_config::process_initialize_weight_table_config,
+    initialize_weight_table_from_previous::process_initialize_weight_table_from_previous,
+    initialize_weight_table_history::process_initialize_weight_table_history,
+    realloc_weight_table_history::process_realloc_weight_table_history,
+    remove_weight::process_remove_weight, set_epochs_before_close::process_set_epochs_before_close,
+    set_price_feed::process_set_price_feed, set_weight_table_admin::process_set_weight_table_admin,
+    unfinalize_weight_table::process_unfinalize_weight_table,
+    update_weight_from_oracle::process_update_weight_from_oracle,
     update_weight_table::process_update_weight_table,
 };
 
 declare_id!(str_to_pubkey(env!("WEIGHT_TABLE_ID")));
 
+/// The reward program, whose epoch merkle roots reference the weight tables
+pub const REWARD_PROGRAM_ID: Pubkey = str_to_pubkey(env!("REWARD_PROGRAM_ID"));
+
 #[cfg(not(feature = "no-entrypoint"))]
 security_txt! {
     // Required fields
@@ -50,15 +76,49 @@ pub fn process_instruction(
         // ------------------------------------------
         // Initialization
         // ------------------------------------------
+        WeightTableInstruction::InitializeWeightTableConfig => {
+            msg!("Instruction: InitializeWeightTableConfig");
+            process_initialize_weight_table_config(program_id, accounts)
+        }
+        WeightTableInstruction::InitializeWeightTableHistory => {
+            msg!("Instruction: InitializeWeightTableHistory");
+            process_initialize_weight_table_history(program_id, accounts)
+        }
+        WeightTableInstruction::ReallocWeightTableHistory => {
+            msg!("Instruction: ReallocWeightTableHistory");
+            process_realloc_weight_table_history(program_id, accounts)
+        }
+        WeightTableInstruction::SetEpochsBeforeClose {
+            epochs_before_close,
+        } => {
+            msg!("Instruction: SetEpochsBeforeClose");
+            process_set_epochs_before_close(program_id, accounts, epochs_before_close)
+        }
         WeightTableInstruction::InitializeWeightTable {
             first_slot_of_ncn_epoch,
         } => {
             msg!("Instruction: InitializeWeightTable");
             process_initialize_weight_table(program_id, accounts, first_slot_of_ncn_epoch)
         }
+        WeightTableInstruction::InitializeWeightTableFromPrevious {
+            previous_ncn_epoch,
+            first_slot_of_ncn_epoch,
+        } => {
+            msg!("Instruction: InitializeWeightTableFromPrevious");
+            process_initialize_weight_table_from_previous(
+                program_id,
+                accounts,
+                previous_ncn_epoch,
+                first_slot_of_ncn_epoch,
+            )
+        }
         // ------------------------------------------
         // Update
         // ------------------------------------------
+        WeightTableInstruction::SetWeightTableAdmin => {
+            msg!("Instruction: SetWeightTableAdmin");
+            process_set_weight_table_admin(program_id, accounts)
+        }
         WeightTableInstruction::UpdateWeightTable {
             ncn_epoch,
             weight_numerator,
@@ -73,6 +133,25 @@ pub fn process_instruction(
                 weight_denominator,
             )
         }
+        WeightTableInstruction::RemoveWeight { ncn_epoch } => {
+            msg!("Instruction: RemoveWeight");
+            process_remove_weight(program_id, accounts, ncn_epoch)
+        }
+        WeightTableInstruction::BatchUpdateWeightTable { ncn_epoch, weights } => {
+            msg!("Instruction: BatchUpdateWeightTable");
+            process_batch_update_weight_table(program_id, accounts, ncn_epoch, weights)
+        }
+        WeightTableInstruction::SetPriceFeed {
+            max_stale_slots,
+            max_confidence_bps,
+        } => {
+            msg!("Instruction: SetPriceFeed");
+            process_set_price_feed(program_id, accounts, max_stale_slots, max_confidence_bps)
+        }
+        WeightTableInstruction::UpdateWeightFromOracle { ncn_epoch } => {
+            msg!("Instruction: UpdateWeightFromOracle");
+            process_update_weight_from_oracle(program_id, accounts, ncn_epoch)
+        }
         // ------------------------------------------
         // Finalization
         // ------------------------------------------
@@ -80,5 +159,13 @@ pub fn process_instruction(
             msg!("Instruction: FinalizeWeightTable");
             process_finalize_weight_table(program_id, accounts, ncn_epoch)
         }
+        WeightTableInstruction::UnfinalizeWeightTable { ncn_epoch } => {
+            msg!("Instruction: UnfinalizeWeightTable");
+            process_unfinalize_weight_table(program_id, accounts, ncn_epoch)
+        }
+        WeightTableInstruction::CloseWeightTable { ncn_epoch } => {
+            msg!("Instruction: CloseWeightTable");
+            process_close_weight_table(program_id, accounts, ncn_epoch)
+        }
     }
 }
@@ -0,0 +1,95 @@
+use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_jsm_core::{
+    loader::{load_signer, load_system_program},
+    realloc,
+};
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::weight_table_history::WeightTableHistory;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+/// Grows the NCN's Weight Table History by up to 10KB, initializing it once it reaches
+/// its full size. Anyone can pay for it
+pub fn process_realloc_weight_table_history(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [ncn, weight_table_history, payer, restaking_program_id, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    load_signer(payer, true)?;
+    load_system_program(system_program)?;
+
+    let (weight_table_history_pubkey, weight_table_history_bump, _) =
+        WeightTableHistory::find_program_address(program_id, ncn.key);
+
+    if weight_table_history_pubkey.ne(weight_table_history.key) {
+        msg!("Incorrect weight table history PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if weight_table_history.owner.ne(program_id) {
+        msg!("Weight table history account is not owned by the program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    if !weight_table_history.is_writable {
+        msg!("Weight table history account is not writable");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if weight_table_history.data.borrow()[0] == WeightTableHistory::DISCRIMINATOR {
+        msg!("Weight table history is already full size");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let new_size = weight_table_history
+        .data_len()
+        .saturating_add(MAX_PERMITTED_DATA_INCREASE)
+        .min(WeightTableHistory::size() as usize);
+
+    msg!(
+        "Growing Weight Table History {} to {} bytes",
+        weight_table_history.key,
+        new_size
+    );
+    realloc(weight_table_history, new_size, payer, &Rent::get()?)?;
+
+    initialize_if_full_size(weight_table_history, ncn.key, weight_table_history_bump)
+}
+
+/// Writes the empty history once the account has grown to its full size
+pub fn initialize_if_full_size(
+    weight_table_history: &AccountInfo,
+    ncn: &Pubkey,
+    weight_table_history_bump: u8,
+) -> ProgramResult {
+    if (weight_table_history.data_len() as u64) < WeightTableHistory::size() {
+        return Ok(());
+    }
+
+    msg!(
+        "Initializing Weight Table History {}",
+        weight_table_history.key
+    );
+
+    let mut weight_table_history_data = weight_table_history.try_borrow_mut_data()?;
+    weight_table_history_data[0] = WeightTableHistory::DISCRIMINATOR;
+    let weight_table_history_account =
+        WeightTableHistory::try_from_slice_unchecked_mut(&mut weight_table_history_data)?;
+
+    *weight_table_history_account = WeightTableHistory::new(*ncn, weight_table_history_bump);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::{
+    error::WeightTableError, weight_table::WeightTable, weight_table_config::WeightTableConfig,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Removes a mint from a Weight Table, freeing its slot
+/// The mint is not loaded so entries for closed mints can be removed as well
+pub fn process_remove_weight(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ncn_epoch: u64,
+) -> ProgramResult {
+    let [ncn, weight_table_config, weight_table, weight_table_admin, mint, restaking_program_id] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+
+    load_signer(weight_table_admin, true)?;
+    WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
+    }
+
+    let mut weight_table_data = weight_table.try_borrow_mut_data()?;
+    let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
+
+    if weight_table_account.finalized() {
+        msg!("Weight table is finalized");
+        return Err(WeightTableError::WeightTableFinalized.into());
+    }
+
+    weight_table_account.remove_weight(mint.key)?;
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::weight_table_config::{PriceFeedEntry, WeightTableConfig};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use switchboard_on_demand::sb_pid;
+
+use crate::token_helpers::load_mint_decimals;
+
+/// Sets the Switchboard price feed used to weight an SPL Token or Token-2022 mint
+pub fn process_set_price_feed(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_stale_slots: u64,
+    max_confidence_bps: u64,
+) -> ProgramResult {
+    let [ncn, weight_table_config, weight_table_admin, mint, price_feed, restaking_program_id] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, true)?;
+    load_signer(weight_table_admin, false)?;
+    load_mint_decimals(mint)?;
+
+    if price_feed.owner.ne(&sb_pid()) {
+        msg!("Price feed is not owned by the Switchboard program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut weight_table_config_data = weight_table_config.try_borrow_mut_data()?;
+    let weight_table_config_account =
+        WeightTableConfig::try_from_slice_unchecked_mut(&mut weight_table_config_data)?;
+
+    weight_table_config_account.check_weight_table_admin(weight_table_admin.key)?;
+
+    msg!(
+        "Setting price feed {} for mint {}",
+        price_feed.key,
+        mint.key
+    );
+    weight_table_config_account.set_price_feed(PriceFeedEntry::new(
+        *mint.key,
+        *price_feed.key,
+        max_stale_slots,
+        max_confidence_bps,
+    ))?;
+
+    Ok(())
+}
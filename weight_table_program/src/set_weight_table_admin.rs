@@ -0,0 +1,54 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::{error::WeightTableError, weight_table_config::WeightTableConfig};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Sets or rotates the weight table admin
+/// Only the NCN admin can change it
+pub fn process_set_weight_table_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [ncn, weight_table_config, ncn_admin, new_weight_table_admin, restaking_program_id] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    let ncn_admin_pubkey = {
+        let ncn_data = ncn.data.borrow();
+        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        ncn.admin
+    };
+
+    load_signer(ncn_admin, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, true)?;
+
+    if ncn_admin_pubkey.ne(ncn_admin.key) {
+        msg!("Incorrect NCN admin");
+        return Err(WeightTableError::IncorrectNcnAdmin.into());
+    }
+
+    let mut weight_table_config_data = weight_table_config.try_borrow_mut_data()?;
+    let weight_table_config_account =
+        WeightTableConfig::try_from_slice_unchecked_mut(&mut weight_table_config_data)?;
+
+    msg!(
+        "Setting weight table admin from {} to {}",
+        weight_table_config_account.weight_table_admin,
+        new_weight_table_admin.key
+    );
+    weight_table_config_account.weight_table_admin = *new_weight_table_admin.key;
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError};
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+
+/// Checks that the mint is an SPL Token or Token-2022 mint and returns its decimals
+pub fn load_mint_decimals(mint: &AccountInfo) -> Result<u8, ProgramError> {
+    if mint.owner.ne(&spl_token::id()) && mint.owner.ne(&spl_token_2022::id()) {
+        msg!("Mint is not owned by SPL Token or Token-2022");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mint_data = mint.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
@@ -0,0 +1,96 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use jito_reward_core::epoch_reward_merkle_root::EpochRewardMerkleRoot;
+use jito_weight_table_core::{
+    error::WeightTableError, weight_table::WeightTable, weight_table_config::WeightTableConfig,
+    weight_table_history::WeightTableHistory,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::REWARD_PROGRAM_ID;
+
+/// Reopens a finalized Weight Table
+/// Only allowed while no operator has voted on the epoch's rewards, which use the table
+/// to normalize stake. The epoch's snapshot in the Weight Table History, if it is initialized,
+/// is flagged as reopened until the table is finalized again
+pub fn process_unfinalize_weight_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ncn_epoch: u64,
+) -> ProgramResult {
+    let [ncn, weight_table_config, weight_table, epoch_reward_merkle_root, weight_table_admin, restaking_program_id, weight_table_history] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+
+    load_signer(weight_table_admin, false)?;
+    WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
+    let update_history =
+        WeightTableHistory::load_if_initialized(program_id, weight_table_history, ncn, true)?;
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
+    }
+
+    let (epoch_reward_merkle_root_pubkey, _, _) =
+        EpochRewardMerkleRoot::find_program_address(&REWARD_PROGRAM_ID, ncn.key, ncn_epoch);
+    if epoch_reward_merkle_root
+        .key
+        .ne(&epoch_reward_merkle_root_pubkey)
+    {
+        msg!("Incorrect epoch reward merkle root PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Voting can't have started if the epoch's merkle root was never initialized
+    if !(epoch_reward_merkle_root.owner.eq(&system_program::id())
+        && epoch_reward_merkle_root.data_is_empty())
+    {
+        EpochRewardMerkleRoot::load(&REWARD_PROGRAM_ID, ncn.key, epoch_reward_merkle_root, false)?;
+
+        let epoch_reward_merkle_root_data = epoch_reward_merkle_root.data.borrow();
+        let epoch_reward_merkle_root =
+            EpochRewardMerkleRoot::try_from_slice_unchecked(&epoch_reward_merkle_root_data)?;
+
+        if epoch_reward_merkle_root.has_votes() {
+            msg!("Weight table is already used by reward voting");
+            return Err(WeightTableError::WeightTableInUse.into());
+        }
+    }
+
+    let mut weight_table_data = weight_table.try_borrow_mut_data()?;
+    let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
+
+    weight_table_account.unfinalize()?;
+
+    msg!(
+        "Weight table {} reopened, reopen count: {}",
+        weight_table.key,
+        weight_table_account.reopen_count()
+    );
+
+    if update_history {
+        let mut weight_table_history_data = weight_table_history.try_borrow_mut_data()?;
+        let weight_table_history_account =
+            WeightTableHistory::try_from_slice_unchecked_mut(&mut weight_table_history_data)?;
+        weight_table_history_account.mark_reopened(ncn_epoch);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::ncn::Ncn;
+use jito_weight_table_core::{
+    error::WeightTableError, oracle::weight_from_price_feed, weight_table::WeightTable,
+    weight_table_config::WeightTableConfig,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+use switchboard_on_demand::{sb_pid, PullFeedAccountData};
+
+use crate::token_helpers::load_mint_decimals;
+
+/// Updates a mint's weight from its configured Switchboard price feed, scaled by the mint's
+/// decimals. Permissionless, the feed's staleness and confidence are checked against the config
+pub fn process_update_weight_from_oracle(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ncn_epoch: u64,
+) -> ProgramResult {
+    let [ncn, weight_table_config, weight_table, mint, price_feed, restaking_program_id] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if restaking_program_id.key.ne(&jito_restaking_program::id()) {
+        msg!("Incorrect restaking program ID");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ncn::load(restaking_program_id.key, ncn, false)?;
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
+    WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
+    let mint_decimals = load_mint_decimals(mint)?;
+
+    if price_feed.owner.ne(&sb_pid()) {
+        msg!("Price feed is not owned by the Switchboard program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let price_feed_entry = {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        *weight_table_config
+            .find_price_feed(mint.key)
+            .ok_or(WeightTableError::PriceFeedNotFound)?
+    };
+
+    if price_feed_entry.price_feed.ne(price_feed.key) {
+        msg!("Price feed is not the one configured for mint {}", mint.key);
+        return Err(WeightTableError::IncorrectPriceFeed.into());
+    }
+
+    let weight = {
+        let price_feed_data = price_feed.data.borrow();
+        let feed = PullFeedAccountData::parse(price_feed_data).map_err(|_| {
+            msg!("Price feed account could not be parsed");
+            ProgramError::InvalidAccountData
+        })?;
+
+        weight_from_price_feed(
+            &feed,
+            mint_decimals,
+            Clock::get()?.slot,
+            price_feed_entry.max_stale_slots(),
+            price_feed_entry.max_confidence_bps(),
+        )?
+    };
+
+    let mut weight_table_data = weight_table.try_borrow_mut_data()?;
+    let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
+
+    if weight_table_account.finalized() {
+        msg!("Weight table is finalized");
+        return Err(WeightTableError::WeightTableFinalized.into());
+    }
+
+    msg!(
+        "Setting weight of mint {} to {}/{}",
+        mint.key,
+        weight.numerator(),
+        weight.denominator()
+    );
+    weight_table_account.set_weight(mint.key, weight)?;
+
+    Ok(())
+}
@@ -1,13 +1,19 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::loader::{load_signer, load_token_mint};
+use jito_jsm_core::loader::load_signer;
 use jito_restaking_core::ncn::Ncn;
-use jito_weight_table_core::{error::WeightTableError, weight::Weight, weight_table::WeightTable};
+use jito_weight_table_core::{
+    error::WeightTableError, weight::Weight, weight_table::WeightTable,
+    weight_table_config::WeightTableConfig,
+};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
     pubkey::Pubkey,
 };
 
-/// Initializes a Weight Table
+use crate::token_helpers::load_mint_decimals;
+
+/// Sets the weight of one mint in a Weight Table, adding the mint if it is not listed yet
+/// Only the weight table admin can call it, and only before the table is finalized
 pub fn process_update_weight_table(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -15,20 +21,17 @@ pub fn process_update_weight_table(
     weight_numerator: u64,
     weight_denominator: u64,
 ) -> ProgramResult {
-    let [ncn, weight_table, weight_table_admin, mint, restaking_program_id] = accounts else {
+    let [ncn, weight_table_config, weight_table, weight_table_admin, mint, restaking_program_id] =
+        accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
     Ncn::load(restaking_program_id.key, ncn, false)?;
-    let ncn_weight_table_admin = {
-        //TODO switch to weight table admin when that is merged
-        let ncn_data = ncn.data.borrow();
-        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
-        ncn.admin
-    };
+    WeightTableConfig::load(program_id, weight_table_config, ncn, false)?;
 
     load_signer(weight_table_admin, true)?;
-    load_token_mint(mint)?;
+    load_mint_decimals(mint)?;
     WeightTable::load(program_id, weight_table, ncn, ncn_epoch, true)?;
 
     if restaking_program_id.key.ne(&jito_restaking_program::id()) {
@@ -36,14 +39,21 @@ pub fn process_update_weight_table(
         return Err(ProgramError::InvalidAccountData);
     }
 
-    if ncn_weight_table_admin.ne(&weight_table_admin.key) {
-        msg!("Vault update delegations ticket is not at the correct PDA");
-        return Err(WeightTableError::IncorrectWeightTableAdmin.into());
+    {
+        let weight_table_config_data = weight_table_config.data.borrow();
+        let weight_table_config =
+            WeightTableConfig::try_from_slice_unchecked(&weight_table_config_data)?;
+        weight_table_config.check_weight_table_admin(weight_table_admin.key)?;
     }
 
     let mut weight_table_data = weight_table.try_borrow_mut_data()?;
     let weight_table_account = WeightTable::try_from_slice_unchecked_mut(&mut weight_table_data)?;
 
+    if weight_table_account.finalized() {
+        msg!("Weight table is finalized");
+        return Err(WeightTableError::WeightTableFinalized.into());
+    }
+
     let weight = Weight::new(weight_numerator, weight_denominator)?;
 
     weight_table_account.set_weight(mint.key, weight)?;